@@ -0,0 +1,61 @@
+use aoc_common::generate::{map_lines, Rng};
+
+use crate::NUMBERS;
+
+// Letters that occur in no spelled out digit, so filler made of them can
+// never form a new one.
+const FILLER_LETTERS: &[u8] = b"abcdjklmpqyz";
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..20)
+        .map(|_| {
+            let mut line = String::new();
+
+            while line.len() < size {
+                match rng.below(8) {
+                    0 => line.push_str(&rng.range(1, 9).to_string()),
+                    1 => line.push_str(rng.pick(&NUMBERS)),
+                    _ => line.push((b'a' + rng.below(26) as u8) as char),
+                }
+            }
+
+            line.truncate(size);
+            line + "\n"
+        })
+        .collect()
+}
+
+// Digits and spelled out digits stay where they are; every other letter is
+// replaced.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    map_lines(input, |line| {
+        let mut kept = vec![false; line.len()];
+        for word in NUMBERS {
+            for (start, _) in line.match_indices(word) {
+                kept[start..start + word.len()].fill(true);
+            }
+        }
+
+        line.char_indices()
+            .map(|(i, c)| {
+                if c.is_ascii_lowercase() && !kept[i] {
+                    rng.pick(FILLER_LETTERS) as char
+                } else {
+                    c
+                }
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_length() {
+        let document = generate(50, &mut Rng::new(1));
+
+        assert!(document.lines().all(|line| line.len() == 50));
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use tracing::{debug, trace};
+
+pub mod inputs;
+
+const NUMBERS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub const EXAMPLES: [&str; 2] = [
+    "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+    "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen",
+];
+
+pub fn sum_calibration_values_part_one(lines: &[&str]) -> u32 {
+    debug!(lines = lines.len(), "summing calibration values");
+    lines
+        .iter()
+        .map(|line| calibration_value_part_one(line))
+        .sum()
+}
+
+fn calibration_value_part_one(line: &str) -> u32 {
+    let mut numbers = line.chars().filter_map(|c| c.to_digit(10)).peekable();
+
+    numbers.peek().unwrap_or(&0) * 10 + numbers.last().unwrap_or(0)
+}
+
+pub fn sum_calibration_values_part_two(lines: &[&str]) -> u32 {
+    debug!(lines = lines.len(), "summing calibration values");
+    lines
+        .iter()
+        .map(|line| calibration_value_part_two(line))
+        .sum()
+}
+
+fn calibration_value_part_two(line: &str) -> u32 {
+    let mut numbers = vec![];
+
+    for (i, c) in line.chars().enumerate() {
+        if let Some(d) = c.to_digit(10) {
+            numbers.push(d);
+            continue;
+        }
+
+        for (j, num) in NUMBERS.iter().enumerate() {
+            let possible_num = line
+                .chars()
+                .skip(i)
+                .filter(|c2| c2.is_alphabetic())
+                .take(num.len());
+
+            if possible_num.clone().count() < num.len() {
+                continue;
+            }
+
+            let is_match = possible_num.zip(num.chars()).all(|(a, b)| a == b);
+
+            if is_match {
+                numbers.push(j as u32 + 1)
+            }
+        }
+    }
+
+    let value = numbers.first().unwrap_or(&0) * 10 + numbers.last().unwrap_or(&0);
+    trace!(line, value, "calibration value");
+
+    value
+}
+
+pub fn part_one(lines: &[&str]) -> u32 {
+    sum_calibration_values_part_one(lines)
+}
+
+pub fn part_two(lines: &[&str]) -> u32 {
+    sum_calibration_values_part_two(lines)
+}
+
+pub struct Trebuchet;
+
+impl Solution for Trebuchet {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(lines).into())
+    }
+
+    fn part_two(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(lines).into())
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "characters per line",
+            base_size: 128,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [true, true],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 1,
+    title: "Trebuchet?!",
+    examples: &EXAMPLES,
+    input: Normalization::PRESERVE,
+    solution: &Trebuchet,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_value_part_one() {
+        assert_eq!(calibration_value_part_one("1abc2"), 12);
+        assert_eq!(calibration_value_part_one("pqr3stu8vwx"), 38);
+        assert_eq!(calibration_value_part_one("a1b2c3d4e5f"), 15);
+        assert_eq!(calibration_value_part_one("treb7uchet"), 77);
+    }
+
+    #[test]
+    fn test_calibration_sum_part_one() {
+        assert_eq!(
+            sum_calibration_values_part_one(&["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"]),
+            142
+        );
+    }
+
+    #[test]
+    fn test_calibration_value_part_two() {
+        assert_eq!(calibration_value_part_two("two1nine"), 29);
+        assert_eq!(calibration_value_part_two("eightwothree"), 83);
+        assert_eq!(calibration_value_part_two("abcone2threexyz"), 13);
+        assert_eq!(calibration_value_part_two("xtwone3four"), 24);
+        assert_eq!(calibration_value_part_two("4nineeightseven2"), 42);
+        assert_eq!(calibration_value_part_two("zoneight234"), 14);
+        assert_eq!(calibration_value_part_two("7pqrstsixteen"), 76);
+    }
+
+    #[test]
+    fn test_calibration_sum_part_two() {
+        assert_eq!(
+            sum_calibration_values_part_two(&[
+                "two1nine",
+                "eightwothree",
+                "abcone2threexyz",
+                "xtwone3four",
+                "4nineeightseven2",
+                "zoneight234",
+                "7pqrstsixteen",
+            ]),
+            281
+        );
+    }
+}
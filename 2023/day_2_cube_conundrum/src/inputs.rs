@@ -0,0 +1,69 @@
+use aoc_common::generate::{map_lines, Rng};
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (1..=size)
+        .map(|id| {
+            let rounds: Vec<String> = (0..rng.range(1, 6))
+                .map(|_| {
+                    let mut colors = vec![];
+                    for color in ["red", "green", "blue"] {
+                        if rng.below(3) > 0 {
+                            colors.push(format!("{} {color}", rng.range(1, 20)));
+                        }
+                    }
+
+                    if colors.is_empty() {
+                        format!("{} red", rng.range(1, 20))
+                    } else {
+                        colors.join(", ")
+                    }
+                })
+                .collect();
+
+            format!("Game {id}: {}\n", rounds.join("; "))
+        })
+        .collect()
+}
+
+// Cube counts are redrawn on the same side of the bag limits of part one.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    map_lines(input, |line| {
+        let Some((game, rounds)) = line.split_once(':') else {
+            return line.to_string();
+        };
+
+        let mut anonymized_rounds = vec![];
+        for round in rounds.split(';') {
+            let mut cubes = vec![];
+            for cube in round.split(',').map(str::trim) {
+                let parsed = cube.split_once(' ').and_then(|(count, color)| {
+                    let limit = match color {
+                        "red" => 12,
+                        "green" => 13,
+                        "blue" => 14,
+                        _ => return None,
+                    };
+                    Some((count.parse::<u64>().ok()?, color, limit))
+                });
+
+                match parsed {
+                    Some((count, color, limit)) => {
+                        let count = if count <= limit {
+                            rng.range(1, limit)
+                        } else {
+                            rng.range(limit + 1, limit + 8)
+                        };
+                        cubes.push(format!("{count} {color}"));
+                    }
+                    None => cubes.push(cube.to_string()),
+                }
+            }
+
+            rng.shuffle(&mut cubes);
+            anonymized_rounds.push(cubes.join(", "));
+        }
+
+        rng.shuffle(&mut anonymized_rounds);
+        format!("{game}: {}", anonymized_rounds.join("; "))
+    })
+}
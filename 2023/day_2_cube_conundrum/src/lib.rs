@@ -0,0 +1,408 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{chosen, Puzzle, Solution},
+};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::trace;
+
+pub mod inputs;
+
+pub const EXAMPLES: [&str; 1] = ["\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"];
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize)]
+pub enum CubeColor {
+    Red(u32),
+    Green(u32),
+    Blue(u32),
+}
+
+impl CubeColor {
+    pub fn amount(&self) -> u32 {
+        match self {
+            CubeColor::Red(n) | CubeColor::Green(n) | CubeColor::Blue(n) => *n,
+        }
+    }
+
+    /// How many cubes of this color the bag holds in part one.
+    pub fn limit(&self) -> u32 {
+        match self {
+            CubeColor::Red(_) => 12,
+            CubeColor::Green(_) => 13,
+            CubeColor::Blue(_) => 14,
+        }
+    }
+}
+
+impl fmt::Display for CubeColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            CubeColor::Red(_) => "red",
+            CubeColor::Green(_) => "green",
+            CubeColor::Blue(_) => "blue",
+        };
+
+        write!(f, "{} {name}", self.amount())
+    }
+}
+
+impl FromStr for CubeColor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let color_amt = s.trim().split(' ').collect::<Vec<_>>();
+
+        if color_amt.len() != 2 {
+            return Err(anyhow!("Cannot parse {}", s));
+        }
+
+        let amount = color_amt[0].parse()?;
+
+        match color_amt[1] {
+            "blue" => Ok(CubeColor::Blue(amount)),
+            "green" => Ok(CubeColor::Green(amount)),
+            "red" => Ok(CubeColor::Red(amount)),
+            _ => Err(anyhow!("Cannot parse {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Vec<CubeColor>>,
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let game_rounds = s.split(':').collect::<Vec<_>>();
+
+        if game_rounds.len() != 2 {
+            return Err(anyhow!("Cannot parse {}", s));
+        }
+
+        let game = game_rounds[0].split(' ').collect::<Vec<_>>();
+
+        if game.len() != 2 {
+            return Err(anyhow!("Cannot parse {}", game_rounds[0]));
+        }
+
+        let id = game[1].parse()?;
+
+        let rounds = game_rounds[1]
+            .split(';')
+            .map(|round_str| {
+                round_str
+                    .split(',')
+                    .map(|color_str| color_str.parse::<CubeColor>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Game { id, rounds })
+    }
+}
+
+impl Game {
+    pub fn is_possible(&self) -> bool {
+        self.first_impossible().is_none()
+    }
+
+    /// The first round, counting from 1, that draws more cubes of a color
+    /// than the bag holds, and that draw.
+    pub fn first_impossible(&self) -> Option<(usize, CubeColor)> {
+        (1..).zip(&self.rounds).find_map(|(i, round)| {
+            round
+                .iter()
+                .find(|color| color.amount() > color.limit())
+                .map(|&color| (i, color))
+        })
+    }
+
+    /// The fewest cubes of each color, red, blue then green, that make the
+    /// game possible.
+    pub fn minimum_set(&self) -> [CubeColor; 3] {
+        self.rounds.iter().fold(
+            [CubeColor::Red(0), CubeColor::Blue(0), CubeColor::Green(0)],
+            |mut acc, colors| {
+                colors.iter().for_each(|&color| {
+                    match color {
+                        CubeColor::Red(_) => acc[0] = std::cmp::max(color, acc[0]),
+                        CubeColor::Blue(_) => acc[1] = std::cmp::max(color, acc[1]),
+                        CubeColor::Green(_) => acc[2] = std::cmp::max(color, acc[2]),
+                    };
+                });
+
+                acc
+            },
+        )
+    }
+
+    pub fn power(&self) -> u32 {
+        self.minimum_set().iter().fold(1, |acc, color| match color {
+            CubeColor::Blue(n) => *n * acc,
+            CubeColor::Green(n) => *n * acc,
+            CubeColor::Red(n) => *n * acc,
+        })
+    }
+}
+
+pub fn possible_ids_sum(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .filter_map(|game| {
+            trace!(id = game.id, possible = game.is_possible(), "checked game");
+            if game.is_possible() {
+                Some(game.id)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+pub fn game_power_sum(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(|game| game.power())
+        .sum()
+}
+
+pub fn explain(games: &[Game], part: u8, item: Option<usize>) -> Result<String> {
+    let mut steps = vec![];
+
+    for (_, game) in chosen(games, item, "games")? {
+        steps.push(match (part, game.first_impossible()) {
+            (1, Some((round, color))) => format!(
+                "Game {}: impossible, round {round} draws {color} but the bag only holds {}",
+                game.id,
+                color.limit()
+            ),
+            (1, None) => format!(
+                "Game {}: possible, no round draws more than 12 red, 13 green or 14 blue",
+                game.id
+            ),
+            _ => {
+                let [red, blue, green] = game.minimum_set();
+                format!(
+                    "Game {}: needs at least {red}, {green} and {blue}, a power of {}",
+                    game.id,
+                    game.power()
+                )
+            }
+        });
+    }
+
+    if item.is_none() {
+        steps.push(match part {
+            1 => format!("The possible games' ids add up to {}", part_one(games)),
+            _ => format!("The powers add up to {}", part_two(games)),
+        });
+    }
+
+    Ok(steps.join("\n"))
+}
+
+pub fn parse(lines: &[&str]) -> Result<Vec<Game>> {
+    lines.iter().map(|line| line.parse()).collect()
+}
+
+pub fn part_one(games: &[Game]) -> u32 {
+    possible_ids_sum(games)
+}
+
+pub fn part_two(games: &[Game]) -> u32 {
+    game_power_sum(games)
+}
+
+pub struct CubeConundrum;
+
+impl Solution for CubeConundrum {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?).into())
+    }
+
+    fn part_two(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?).into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn explain(&self, lines: &[&str], part: u8, item: Option<usize>) -> Result<String> {
+        explain(&parse(lines)?, part, item)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "games",
+            base_size: 128,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [true, false],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 2,
+    title: "Cube Conundrum",
+    examples: &EXAMPLES,
+    input: Normalization::TRIM,
+    solution: &CubeConundrum,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_possibility() {
+        assert!("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse::<Game>()
+            .unwrap()
+            .is_possible());
+        assert!(
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"
+                .parse::<Game>()
+                .unwrap()
+                .is_possible()
+        );
+        assert!(
+            !"Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+                .parse::<Game>()
+                .unwrap()
+                .is_possible()
+        );
+        assert!(
+            !"Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red"
+                .parse::<Game>()
+                .unwrap()
+                .is_possible()
+        );
+        assert!("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+            .parse::<Game>()
+            .unwrap()
+            .is_possible());
+    }
+
+    #[test]
+    fn test_possible_game_sum() {
+        let lines = &[
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+        ];
+
+        let games: Vec<Game> = lines
+        .iter()
+        .map(|line| line.parse().unwrap())
+        .collect();
+
+        assert_eq!(
+            possible_ids_sum(&games),
+            8
+        )
+    }
+
+    #[test]
+    fn test_explain_impossible_game() {
+        let lines: Vec<_> = EXAMPLES[0].lines().collect();
+        let games = parse(&lines).unwrap();
+
+        assert_eq!(
+            explain(&games, 1, Some(3)).unwrap(),
+            "Game 3: impossible, round 1 draws 20 red but the bag only holds 12"
+        );
+        assert_eq!(
+            explain(&games, 2, Some(1)).unwrap(),
+            "Game 1: needs at least 4 red, 2 green and 6 blue, a power of 48"
+        );
+        assert!(explain(&games, 1, None).unwrap().ends_with("add up to 8"));
+    }
+
+    #[test]
+    fn test_game_power() {
+        assert_eq!(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+                .parse::<Game>()
+                .unwrap()
+                .power(),
+            48
+        );
+        assert_eq!(
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"
+                .parse::<Game>()
+                .unwrap()
+                .power(),
+            12
+        );
+        assert_eq!(
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+                .parse::<Game>()
+                .unwrap()
+                .power(),
+            1560
+        );
+        assert_eq!(
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red"
+                .parse::<Game>()
+                .unwrap()
+                .power(),
+            630
+        );
+        assert_eq!(
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+                .parse::<Game>()
+                .unwrap()
+                .power(),
+            36
+        );
+    }
+
+    #[test]
+    fn test_game_power_sum() {
+        let lines = &[
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+        ];
+
+        let games: Vec<Game> = lines
+        .iter()
+        .map(|line| line.parse().unwrap())
+        .collect();
+        
+        assert_eq!(
+            game_power_sum(&games),
+            2286
+        )
+    }
+}
@@ -0,0 +1,44 @@
+use aoc_common::generate::{map_lines, map_numbers, same_length_number, Rng};
+
+const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+const NON_GEAR_SYMBOLS: &[u8] = b"#+$/=%@&-";
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let width = 140;
+
+    (0..size.max(3))
+        .map(|_| {
+            let mut row = String::new();
+
+            while row.len() < width {
+                match rng.below(10) {
+                    0 | 1 => row.push_str(&rng.range(1, 999).to_string()),
+                    2 => row.push(rng.pick(SYMBOLS) as char),
+                    _ => row.push('.'),
+                }
+                row.push('.');
+            }
+
+            row.truncate(width);
+            row + "\n"
+        })
+        .collect()
+}
+
+// Part numbers and symbols are redrawn in place, keeping gears as gears.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    map_lines(input, |line| {
+        let numbers = map_numbers(line, |digits| same_length_number(digits, rng));
+
+        numbers
+            .chars()
+            .map(|c| {
+                if c.is_ascii_digit() || c.is_whitespace() || c == '.' || c == '*' {
+                    c
+                } else {
+                    rng.pick(NON_GEAR_SYMBOLS) as char
+                }
+            })
+            .collect()
+    })
+}
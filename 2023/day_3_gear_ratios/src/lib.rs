@@ -0,0 +1,353 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::debug;
+
+pub mod inputs;
+
+pub const EXAMPLES: [&str; 1] = ["\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."];
+
+#[derive(PartialEq, Eq, Debug, Serialize)]
+pub enum SchematicParts {
+    Number(u32),
+    Symbol(bool),
+    Empty,
+}
+
+/// The schematic as a grid, which has to be rectangular. Whitespace has no
+/// meaning in a schematic, so a line with any is rejected rather than read as
+/// a symbol.
+pub fn lines_to_matrix(lines: &[&str]) -> Result<Vec<Vec<SchematicParts>>> {
+    let width = lines.first().map_or(0, |line| line.chars().count());
+    if width == 0 {
+        bail!("The schematic is empty");
+    }
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(j, c)| match c {
+                    '.' => Ok(SchematicParts::Empty),
+                    c if c.is_whitespace() => Err(anyhow!(
+                        "Row {} has whitespace in column {}, where only digits, '.' and symbols \
+                         belong",
+                        i + 1,
+                        j + 1
+                    )),
+                    c => Ok(match c.to_digit(10) {
+                        Some(digit) => SchematicParts::Number(digit),
+                        None => SchematicParts::Symbol(c == '*'),
+                    }),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            if row.len() != width {
+                bail!(
+                    "Row {} is {} wide, but the schematic is {width} wide",
+                    i + 1,
+                    row.len()
+                );
+            }
+
+            Ok(row)
+        })
+        .collect()
+}
+
+pub fn generate_symbol_fields(schematic: &[Vec<SchematicParts>]) -> (Vec<Vec<bool>>, Vec<Vec<usize>>) {
+    let height = schematic.len();
+    let width = schematic.first().map_or(0, |line| line.len());
+
+    let mut symbol_fields: Vec<_> = (0..height)
+        .map(|_| (0..width).map(|_| false).collect::<Vec<_>>())
+        .collect();
+
+    let mut gear_fields: Vec<_> = (0..height)
+        .map(|_| (0..width).map(|_| 0).collect::<Vec<_>>())
+        .collect();
+
+    let mut gear_num = 0;
+    for (i, schematic_line) in schematic
+        .iter()
+        .enumerate()
+        .take(height.saturating_sub(1))
+        .skip(1)
+    {
+        for (j, schematic_part) in schematic_line
+            .iter()
+            .enumerate()
+            .take(width.saturating_sub(1))
+            .skip(1)
+        {
+            let gear = match schematic_part {
+                SchematicParts::Number(_) | SchematicParts::Empty => continue,
+                SchematicParts::Symbol(gear) => gear,
+            };
+
+            symbol_fields[i - 1][j - 1] = true;
+            symbol_fields[i][j - 1] = true;
+            symbol_fields[i + 1][j - 1] = true;
+            symbol_fields[i - 1][j] = true;
+            symbol_fields[i][j] = true;
+            symbol_fields[i + 1][j] = true;
+            symbol_fields[i - 1][j + 1] = true;
+            symbol_fields[i][j + 1] = true;
+            symbol_fields[i + 1][j + 1] = true;
+
+            if *gear {
+                gear_num += 1;
+
+                gear_fields[i - 1][j - 1] = gear_num;
+                gear_fields[i][j - 1] = gear_num;
+                gear_fields[i + 1][j - 1] = gear_num;
+                gear_fields[i - 1][j] = gear_num;
+                gear_fields[i][j] = gear_num;
+                gear_fields[i + 1][j] = gear_num;
+                gear_fields[i - 1][j + 1] = gear_num;
+                gear_fields[i][j + 1] = gear_num;
+                gear_fields[i + 1][j + 1] = gear_num;
+            }
+        }
+    }
+
+    debug!(height, width, gears = gear_num, "generated symbol fields");
+
+    (symbol_fields, gear_fields)
+}
+
+fn digits_to_num(digits: &[u32]) -> u32 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| digit * 10_u32.pow(i as u32))
+        .sum()
+}
+
+pub fn part_number_sum_part_one(schematic: &[Vec<SchematicParts>], symbol_fields: &[Vec<bool>]) -> u32 {
+    schematic
+        .iter()
+        .enumerate()
+        .flat_map(|(i, schematic_line)| {
+            let mut part_numbers = vec![];
+
+            let mut touching_symbol = false;
+            let mut digit_collector = vec![];
+            for (j, schematic_part) in schematic_line
+                .iter()
+                .chain([&SchematicParts::Empty])
+                .enumerate()
+            {
+                if let SchematicParts::Number(digit) = schematic_part {
+                    digit_collector.push(*digit);
+                    touching_symbol |= symbol_fields[i][j];
+                } else if !digit_collector.is_empty() {
+                    if touching_symbol {
+                        part_numbers.push(digits_to_num(&digit_collector));
+                    }
+
+                    touching_symbol = false;
+                    digit_collector = vec![];
+                }
+            }
+
+            part_numbers
+        })
+        .sum()
+}
+
+pub fn part_number_sum_part_two(schematic: &[Vec<SchematicParts>], gear_fields: &[Vec<usize>]) -> u32 {
+    schematic
+        .iter()
+        .enumerate()
+        .fold(
+            HashMap::new(),
+            |mut acc: HashMap<usize, Vec<u32>>, (i, schematic_line)| {
+                let mut touching_gears = HashSet::new();
+                let mut digit_collector = vec![];
+
+                for (j, schematic_part) in schematic_line
+                    .iter()
+                    .chain([&SchematicParts::Empty])
+                    .enumerate()
+                {
+                    if let SchematicParts::Number(digit) = schematic_part {
+                        digit_collector.push(*digit);
+                        let gear = gear_fields[i][j];
+                        if gear != 0 {
+                            touching_gears.insert(gear);
+                        }
+                    } else if !digit_collector.is_empty() {
+                        let num = digits_to_num(&digit_collector);
+
+                        for gear in touching_gears {
+                            if let Some(num_vec) = acc.get_mut(&gear) {
+                                num_vec.push(num)
+                            } else {
+                                acc.insert(gear, vec![num]);
+                            }
+                        }
+
+                        touching_gears = HashSet::new();
+                        digit_collector = vec![];
+                    }
+                }
+
+                acc
+            },
+        )
+        .values()
+        .filter_map(|connected_nums| {
+            if connected_nums.len() == 2 {
+                Some(connected_nums.iter().product::<u32>())
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+pub fn parse(lines: &[&str]) -> Result<Vec<Vec<SchematicParts>>> {
+    lines_to_matrix(lines)
+}
+
+pub fn part_one(schematic: &[Vec<SchematicParts>]) -> u32 {
+    let (symbol_fields, _) = generate_symbol_fields(schematic);
+    part_number_sum_part_one(schematic, &symbol_fields)
+}
+
+pub fn part_two(schematic: &[Vec<SchematicParts>]) -> u32 {
+    let (_, gear_fields) = generate_symbol_fields(schematic);
+    part_number_sum_part_two(schematic, &gear_fields)
+}
+
+pub struct GearRatios;
+
+impl Solution for GearRatios {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?).into())
+    }
+
+    fn part_two(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?).into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "schematic rows",
+            base_size: 16,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [false, false],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 3,
+    title: "Gear Ratios",
+    examples: &EXAMPLES,
+    input: Normalization::PRESERVE,
+    solution: &GearRatios,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_number_sum_part_one() {
+        let lines = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ];
+
+        let schematic = lines_to_matrix(&lines).unwrap();
+
+        let (symbol_fields, _) = generate_symbol_fields(&schematic);
+
+        assert_eq!(part_number_sum_part_one(&schematic, &symbol_fields), 4361)
+    }
+
+    #[test]
+    fn test_part_number_sum_part_two() {
+        let lines = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ];
+
+        let schematic = lines_to_matrix(&lines).unwrap();
+
+        let (_, gear_fields) = generate_symbol_fields(&schematic);
+
+        assert_eq!(part_number_sum_part_two(&schematic, &gear_fields), 467835)
+    }
+
+    #[test]
+    fn test_irregular_schematics_are_rejected() {
+        let error = |lines: &[&str]| lines_to_matrix(lines).unwrap_err().to_string();
+
+        assert_eq!(
+            error(&["467..", "...*.", "..35"]),
+            "Row 3 is 4 wide, but the schematic is 5 wide"
+        );
+        assert_eq!(
+            error(&["467..", "...*.", "  ..35"]),
+            "Row 3 has whitespace in column 1, where only digits, '.' and symbols belong"
+        );
+        assert_eq!(error(&[]), "The schematic is empty");
+        assert_eq!(error(&[""]), "The schematic is empty");
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::generate::{map_lines, map_numbers, Rng};
+
+// Real cards win only a few copies each; many matches per card would make
+// the copy counts of part two grow exponentially and overflow.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let format_numbers = |numbers: &[u64]| {
+        numbers
+            .iter()
+            .map(|n| format!("{n:>2}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    (1..=size)
+        .map(|id| {
+            let mut winning_numbers: Vec<u64> = vec![];
+            while winning_numbers.len() < 10 {
+                let n = rng.range(1, 49);
+                if !winning_numbers.contains(&n) {
+                    winning_numbers.push(n);
+                }
+            }
+
+            let mut number_pool: Vec<u64> = (0..25).map(|_| rng.range(50, 99)).collect();
+
+            let matches = match rng.below(20) {
+                0..=9 => 0,
+                10..=16 => 1,
+                _ => 2,
+            };
+            number_pool[..matches].copy_from_slice(&winning_numbers[..matches]);
+
+            format!(
+                "Card {id:>4}: {} | {}\n",
+                format_numbers(&winning_numbers),
+                format_numbers(&number_pool)
+            )
+        })
+        .collect()
+}
+
+// Numbers are swapped through one consistent permutation, so every card keeps
+// its number of matches.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    let numbers: HashSet<String> = input
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .flat_map(|(_, numbers)| numbers.split(|c: char| !c.is_ascii_digit()))
+        .filter(|number| !number.is_empty() && number.len() <= 4)
+        .map(str::to_string)
+        .collect();
+
+    let mut numbers: Vec<String> = numbers.into_iter().collect();
+    numbers.sort();
+
+    let mut permutation = HashMap::new();
+    for length in 1..=4 {
+        let lowest = if length == 1 {
+            0
+        } else {
+            10_u64.pow(length - 1)
+        };
+        let mut candidates: Vec<u64> = (lowest..10_u64.pow(length)).collect();
+        rng.shuffle(&mut candidates);
+
+        let originals = numbers
+            .iter()
+            .filter(|number| number.len() == length as usize);
+        for (original, replacement) in originals.zip(candidates) {
+            permutation.insert(original.clone(), replacement.to_string());
+        }
+    }
+
+    map_lines(input, |line| match line.split_once(':') {
+        Some((card, numbers)) => {
+            let numbers = map_numbers(numbers, |number| {
+                permutation
+                    .get(number)
+                    .cloned()
+                    .unwrap_or(number.to_string())
+            });
+            format!("{card}:{numbers}")
+        }
+        None => line.to_string(),
+    })
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error, Result};
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use tracing::trace;
+
+pub mod inputs;
+
+pub const EXAMPLES: [&str; 1] = ["\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"];
+
+#[derive(Debug, Serialize)]
+pub struct Scratchcard {
+    pub id: usize,
+    pub winning_numbers: Vec<u32>,
+    #[serde(serialize_with = "sorted_pool")]
+    pub number_pool: HashSet<u32>,
+}
+
+// Set order changes from run to run, so dumps list the pool in order.
+fn sorted_pool<S: Serializer>(pool: &HashSet<u32>, serializer: S) -> Result<S::Ok, S::Error> {
+    pool.iter().collect::<BTreeSet<_>>().serialize(serializer)
+}
+
+impl FromStr for Scratchcard {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let card_id_nums = s.split(':').collect::<Vec<_>>();
+
+        if card_id_nums.len() != 2 {
+            return Err(anyhow!("Cannot parse {}", s));
+        }
+
+        let card_id = card_id_nums[0]
+            .split(' ')
+            .filter(|card_id_split| !card_id_split.is_empty())
+            .collect::<Vec<_>>();
+
+        if card_id.len() != 2 {
+            return Err(anyhow!("Cannot parse {}", card_id_nums[0]));
+        }
+
+        let id = card_id[1].trim().parse()?;
+
+        let nums = card_id_nums[1].split('|').collect::<Vec<_>>();
+        if nums.len() != 2 {
+            return Err(anyhow!("Cannot parse {}", card_id_nums[1]));
+        }
+
+        let winning_numbers = nums[0]
+            .trim()
+            .split(" ")
+            .map(|n_str| n_str.trim())
+            .filter(|n_str| !n_str.is_empty())
+            .map(|n_str| n_str.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        let number_pool = nums[1]
+            .trim()
+            .split(" ")
+            .map(|n_str| n_str.trim())
+            .filter(|n_str| !n_str.is_empty())
+            .map(|n_str| n_str.parse())
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        Ok(Scratchcard {
+            id,
+            winning_numbers,
+            number_pool,
+        })
+    }
+}
+
+impl Scratchcard {
+    pub fn matches(&self) -> usize {
+        self.winning_numbers
+            .iter()
+            .filter(|num| self.number_pool.contains(num))
+            .count()
+    }
+}
+
+pub fn stack_winnings_part_one(cards: &[Scratchcard]) -> u32 {
+    cards
+        .iter()
+        .map(|card| card.matches())
+        .map(|matches| {
+            if matches > 0 {
+                2_u32.pow(matches as u32 - 1)
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+pub fn stack_winnings_part_two(cards: &[Scratchcard]) -> u32 {
+    let max_card_id = cards.len();
+
+    let initial_stack = (1..=max_card_id)
+        .map(|id| (id, 1))
+        .collect::<HashMap<usize, u32>>();
+
+    cards
+        .iter()
+        .fold(initial_stack, |mut acc, card| {
+            let copy_count = *acc.get_mut(&card.id).unwrap_or(&mut 0);
+            trace!(
+                id = card.id,
+                copy_count,
+                matches = card.matches(),
+                "scratched card"
+            );
+
+            (1..=card.matches())
+                .map(|i| card.id + i)
+                .filter(|&copied_id| copied_id <= max_card_id)
+                .for_each(|copied_id| {
+                    let prev_copies = acc.get(&copied_id).unwrap_or(&0);
+                    acc.insert(copied_id, prev_copies + copy_count);
+                });
+
+            acc
+        })
+        .into_values()
+        .sum()
+}
+
+pub fn parse(lines: &[&str]) -> Result<Vec<Scratchcard>> {
+    lines.iter().map(|line| line.parse()).collect()
+}
+
+pub fn part_one(cards: &[Scratchcard]) -> u32 {
+    stack_winnings_part_one(cards)
+}
+
+pub fn part_two(cards: &[Scratchcard]) -> u32 {
+    stack_winnings_part_two(cards)
+}
+
+pub struct Scratchcards;
+
+impl Solution for Scratchcards {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?).into())
+    }
+
+    fn part_two(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?).into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "cards",
+            base_size: 128,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [true, true],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 4,
+    title: "Scratchcards",
+    examples: &EXAMPLES,
+    input: Normalization::TRIM,
+    solution: &Scratchcards,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_winnings_part_one() {
+        let lines = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+
+        let cards: Vec<Scratchcard> = lines
+            .iter()
+            .map(|line| line.parse::<Scratchcard>().unwrap())
+            .collect();
+
+        assert_eq!(stack_winnings_part_one(&cards), 13)
+    }
+
+    #[test]
+    fn test_stack_winnings_part_two() {
+        let lines = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+
+        let cards: Vec<Scratchcard> = lines
+            .iter()
+            .map(|line| line.parse::<Scratchcard>().unwrap())
+            .collect();
+
+        assert_eq!(stack_winnings_part_two(&cards), 30)
+    }
+}
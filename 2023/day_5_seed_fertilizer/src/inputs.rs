@@ -0,0 +1,111 @@
+use aoc_common::generate::{map_lines, map_numbers, Rng};
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let seed_ranges = 10;
+    let range_len = (size / seed_ranges).max(1) as u64;
+
+    let seeds: Vec<String> = (0..seed_ranges)
+        .map(|_| format!("{} {range_len}", rng.below(1_000_000)))
+        .collect();
+
+    let maps: Vec<String> = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ]
+    .iter()
+    .map(|name| {
+        let ranges: Vec<String> = (0..10)
+            .map(|_| {
+                format!(
+                    "{} {} {}",
+                    rng.below(1_000_000),
+                    rng.below(1_000_000),
+                    rng.range(1_000, 100_000)
+                )
+            })
+            .collect();
+
+        format!("{name} map:\n{}\n", ranges.join("\n"))
+    })
+    .collect();
+
+    format!("seeds: {}\n\n{}", seeds.join(" "), maps.join("\n"))
+}
+
+// Every seed range start and map range moves by one offset, which keeps how
+// ranges overlap and how unmapped values pass through. Range lengths are kept.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    let offset = rng.range(1_000_000, 1_000_000_000);
+    let shift = |digits: &str| {
+        digits
+            .parse::<u64>()
+            .map_or(digits.to_string(), |n| (n + offset).to_string())
+    };
+
+    let mut in_map = false;
+    map_lines(input, |line| {
+        if let Some(seeds) = line.strip_prefix("seeds:") {
+            let mut index = 0;
+            let seeds = map_numbers(seeds, |digits| {
+                index += 1;
+                if index % 2 == 1 {
+                    shift(digits)
+                } else {
+                    digits.to_string()
+                }
+            });
+            return format!("seeds:{seeds}");
+        }
+
+        if line.ends_with("map:") {
+            in_map = true;
+            return line.to_string();
+        }
+
+        if !in_map {
+            return line.to_string();
+        }
+
+        let mut index = 0;
+        map_numbers(line, |digits| {
+            index += 1;
+            if index <= 2 {
+                shift(digits)
+            } else {
+                digits.to_string()
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::cancel::CancelToken;
+
+    use super::*;
+    use crate::{parse, part_two, EXAMPLES};
+
+    #[test]
+    fn test_ranges_shift_together() {
+        let lowest = |input: &str| {
+            let lines: Vec<&str> = input.lines().map(str::trim).collect();
+            part_two(&parse(&lines).unwrap(), &CancelToken::new()).unwrap()
+        };
+        let anonymized = anonymize(EXAMPLES[0], &mut Rng::new(5));
+
+        let seed_shift = anonymized.lines().next().unwrap()["seeds: ".len()..]
+            .split(' ')
+            .next()
+            .unwrap()
+            .parse::<usize>()
+            .unwrap()
+            - 79;
+
+        assert_eq!(lowest(&anonymized) - lowest(EXAMPLES[0]), seed_shift);
+    }
+}
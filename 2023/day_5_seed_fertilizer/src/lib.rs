@@ -0,0 +1,311 @@
+use anyhow::{anyhow, bail, Result};
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::debug;
+use winnow::{
+    ascii::digit1,
+    combinator::{preceded, separated},
+    PResult, Parser,
+};
+
+pub mod inputs;
+
+pub const EXAMPLES: [&str; 1] = ["\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"];
+
+#[derive(Debug, Serialize)]
+pub struct SeedsAndMaps {
+    pub seeds: Vec<usize>,
+    pub seed_to_soil: Vec<(usize, usize, usize)>,
+    pub soil_to_fertilizer: Vec<(usize, usize, usize)>,
+    pub fertilizer_to_water: Vec<(usize, usize, usize)>,
+    pub water_to_light: Vec<(usize, usize, usize)>,
+    pub light_to_temperature: Vec<(usize, usize, usize)>,
+    pub temperature_to_humidity: Vec<(usize, usize, usize)>,
+    pub humidity_to_location: Vec<(usize, usize, usize)>,
+}
+
+impl SeedsAndMaps {
+    pub fn new(lines: &[&str]) -> Result<SeedsAndMaps> {
+        let mut lines_iter = lines.iter().map(|s| s.trim()).filter(|s| !s.is_empty());
+
+        let seed_line_str = lines_iter.next().ok_or(anyhow!("no seed line"))?;
+        let mut seed_line = seed_line_str;
+        let seeds = preceded("seeds: ", parse_number_line)
+            .parse_next(&mut seed_line)
+            .map_err(|e| anyhow!(e.to_string()))?;
+
+        let mut mappings = [vec![], vec![], vec![], vec![], vec![], vec![], vec![]];
+        let mut current_mapping = 0;
+        for mut line in lines_iter.skip(1) {
+            if line.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+                let numbers = parse_number_line
+                    .parse_next(&mut line)
+                    .map_err(|e| anyhow!(e.to_string()))?;
+                let [d, s, l] = numbers[..] else {
+                    bail!("a map line has {} numbers instead of 3", numbers.len());
+                };
+                mappings
+                    .get_mut(current_mapping)
+                    .ok_or(anyhow!("more than 7 maps"))?
+                    .push((d, s, l));
+            } else {
+                current_mapping += 1;
+            }
+        }
+
+        Ok(SeedsAndMaps {
+            seeds,
+            seed_to_soil: mappings[0].clone(),
+            soil_to_fertilizer: mappings[1].clone(),
+            fertilizer_to_water: mappings[2].clone(),
+            water_to_light: mappings[3].clone(),
+            light_to_temperature: mappings[4].clone(),
+            temperature_to_humidity: mappings[5].clone(),
+            humidity_to_location: mappings[6].clone(),
+        })
+    }
+
+    pub fn min_seed_range_location(&self, cancel: &CancelToken) -> Result<usize> {
+        let ranges = || self.seeds.windows(2).step_by(2);
+        let seed_count = ranges().map(|window| window[1] as u64).sum();
+        let mut progress = cancel.progress("seeds", Some(seed_count));
+        let mut lowest = usize::MAX;
+
+        for window in ranges() {
+            let [start, len, ..] = window else {
+                continue;
+            };
+
+            let mut min_location = usize::MAX;
+            for seed in *start..start + len {
+                progress.tick()?;
+                min_location = min_location.min(self.map_seed(seed));
+            }
+            debug!(start, len, min_location, "mapped seed range");
+
+            lowest = lowest.min(min_location);
+        }
+
+        Ok(lowest)
+    }
+
+    pub fn min_seed_location(&self) -> usize {
+        self.seeds
+            .iter()
+            .map(|seed| self.map_seed(*seed))
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+
+    pub fn map_seed(&self, seed: usize) -> usize {
+        let soil = map_input(seed, &self.seed_to_soil);
+        let fertilizer = map_input(soil, &self.soil_to_fertilizer);
+        let water = map_input(fertilizer, &self.fertilizer_to_water);
+        let light = map_input(water, &self.water_to_light);
+        let temperature = map_input(light, &self.light_to_temperature);
+        let humidity = map_input(temperature, &self.temperature_to_humidity);
+
+        map_input(humidity, &self.humidity_to_location)
+    }
+}
+
+fn parse_digits(input: &mut &str) -> PResult<usize> {
+    digit1.parse_to().parse_next(input)
+}
+
+fn parse_number_line(input: &mut &str) -> PResult<Vec<usize>> {
+    separated(0.., parse_digits, " ").parse_next(input)
+}
+
+fn map_input(input: usize, mappings: &[(usize, usize, usize)]) -> usize {
+    mappings
+        .iter()
+        .filter_map(|(d_start, s_start, len)| {
+            if (*s_start..(s_start + len)).contains(&input) {
+                Some(d_start + (input - s_start))
+            } else {
+                None
+            }
+        })
+        .next()
+        .unwrap_or(input)
+}
+
+pub fn parse(lines: &[&str]) -> Result<SeedsAndMaps> {
+    SeedsAndMaps::new(lines)
+}
+
+pub fn part_one(seed_maps: &SeedsAndMaps) -> usize {
+    seed_maps.min_seed_location()
+}
+
+pub fn part_two(seed_maps: &SeedsAndMaps, cancel: &CancelToken) -> Result<usize> {
+    seed_maps.min_seed_range_location(cancel)
+}
+
+pub struct SeedFertilizer;
+
+impl Solution for SeedFertilizer {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?).into())
+    }
+
+    fn part_two(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?, cancel)?.into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "seeds in ranges",
+            base_size: 4096,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [false, false],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 5,
+    title: "If You Give A Seed A Fertilizer",
+    examples: &EXAMPLES,
+    input: Normalization::TRIM,
+    solution: &SeedFertilizer,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "
+    seeds: 79 14 55 13
+
+    seed-to-soil map:
+    50 98 2
+    52 50 48
+
+    soil-to-fertilizer map:
+    0 15 37
+    37 52 2
+    39 0 15
+
+    fertilizer-to-water map:
+    49 53 8
+    0 11 42
+    42 0 7
+    57 7 4
+
+    water-to-light map:
+    88 18 7
+    18 25 70
+
+    light-to-temperature map:
+    45 77 23
+    81 45 19
+    68 64 13
+
+    temperature-to-humidity map:
+    0 69 1
+    1 0 69
+
+    humidity-to-location map:
+    60 56 37
+    56 93 4";
+
+    #[test]
+    fn test_part_one() {
+        let test_lines: Vec<&str> = TEST_INPUT.split("\n").collect();
+
+        let seed_maps = SeedsAndMaps::new(&test_lines).unwrap();
+        assert_eq!(seed_maps.min_seed_location(), 35);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let test_lines: Vec<&str> = TEST_INPUT.split("\n").collect();
+
+        let seed_maps = SeedsAndMaps::new(&test_lines).unwrap();
+        assert_eq!(
+            seed_maps
+                .min_seed_range_location(&CancelToken::new())
+                .unwrap(),
+            46
+        );
+    }
+
+    #[test]
+    fn test_part_two_cancelled() {
+        let test_lines: Vec<&str> = TEST_INPUT.split("\n").collect();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+
+        let seed_maps = SeedsAndMaps::new(&test_lines).unwrap();
+        assert!(seed_maps.min_seed_range_location(&cancel).is_err());
+    }
+
+    #[test]
+    fn test_malformed_maps_are_rejected() {
+        let short = ["seeds: 1 2", "", "seed-to-soil map:", "50 98"];
+        assert!(SeedsAndMaps::new(&short).is_err());
+
+        let mut eight_maps = vec!["seeds: 1 2", ""];
+        for _ in 0..8 {
+            eight_maps.extend(["a-to-b map:", "50 98 2"]);
+        }
+        assert!(SeedsAndMaps::new(&eight_maps).is_err());
+        assert!(SeedsAndMaps::new(&eight_maps[..16]).is_ok());
+    }
+}
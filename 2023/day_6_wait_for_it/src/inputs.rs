@@ -0,0 +1,52 @@
+use aoc_common::generate::{map_lines, map_numbers, Rng};
+
+// Part two concatenates every race into one, so a single race keeps the
+// numbers within a u64 no matter how long it lasts.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let time = size as u64;
+    let distance = rng.below(time * time / 4);
+
+    format!("Time: {time}\nDistance: {distance}\n")
+}
+
+// Record distances are nudged, staying beatable when they were.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    let times: Vec<u64> = input
+        .lines()
+        .find_map(|line| line.strip_prefix("Time:"))
+        .map(|times| {
+            times
+                .split_whitespace()
+                .filter_map(|time| time.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    map_lines(input, |line| {
+        let Some(distances) = line.strip_prefix("Distance:") else {
+            return line.to_string();
+        };
+
+        let mut index = 0;
+        let distances = map_numbers(distances, |digits| {
+            index += 1;
+            match (digits.parse::<u64>(), times.get(index - 1)) {
+                (Ok(distance), Some(&time)) => {
+                    let best = (time / 2) * (time - time / 2);
+                    let low = distance - distance / 10;
+                    let high = (distance + distance / 10).max(low);
+                    let nudged = rng.range(low, high);
+
+                    if distance < best {
+                        nudged.min(best - 1).to_string()
+                    } else {
+                        nudged.max(best).to_string()
+                    }
+                }
+                _ => digits.to_string(),
+            }
+        });
+
+        format!("Distance:{distances}")
+    })
+}
@@ -0,0 +1,198 @@
+use anyhow::{anyhow, Result};
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::debug;
+use winnow::{
+    ascii::{digit1, multispace0},
+    combinator::{preceded, repeat, terminated},
+    PResult, Parser,
+};
+
+pub mod inputs;
+
+pub const EXAMPLES: [&str; 1] = ["\
+Time:      7  15   30
+Distance:  9  40  200"];
+
+#[derive(Debug, Serialize)]
+pub struct Race {
+    pub time: u64,
+    pub distance: u64,
+}
+
+#[derive(Serialize)]
+pub struct Races {
+    pub races: Vec<Race>,
+    pub big_race: Race,
+}
+
+pub fn construct_races(lines: &[&str]) -> Result<(Vec<Race>, Race)> {
+    let filtered_lines: Vec<&str> = lines
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let mut time_line = *filtered_lines.first().ok_or(anyhow!("no time line"))?;
+    let times = preceded(("Time:", multispace0), parse_number_line)
+        .parse_next(&mut time_line)
+        .map_err(|e| anyhow!(e.to_string()))?;
+
+    let mut distance_line = *filtered_lines.get(1).ok_or(anyhow!("no distance line"))?;
+    let distances = preceded(("Distance:", multispace0), parse_number_line)
+        .parse_next(&mut distance_line)
+        .map_err(|e| anyhow!(e.to_string()))?;
+
+    let races = times
+        .iter()
+        .zip(distances.iter())
+        .map(|(&time, &distance)| Race { time, distance })
+        .collect();
+
+    let big_time: String = times.iter().map(|t| t.to_string()).collect();
+    let big_distance: String = distances.iter().map(|d| d.to_string()).collect();
+
+    let big_race = Race { time: big_time.parse::<u64>()?, distance: big_distance.parse::<u64>()? };
+
+    Ok((races, big_race))
+}
+
+fn parse_digits(input: &mut &str) -> PResult<u64> {
+    digit1.parse_to().parse_next(input)
+}
+
+fn parse_number_line(input: &mut &str) -> PResult<Vec<u64>> {
+    repeat(0.., terminated(parse_digits, multispace0)).parse_next(input)
+}
+
+fn is_whole(n: f64) -> bool {
+    (n - n.round()).abs() < 0.00001
+}
+
+pub fn winning_possibilities(race: &Race) -> u64 {
+    let left = (race.time as f64) * 0.5;
+    let right = ((race.time.pow(2) as f64) * 0.25 - race.distance as f64).sqrt();
+
+    if right < 0_f64 {
+        return 0;
+    }
+
+    let min_f = left - right;
+    let max_f = left + right;
+
+    let min = if is_whole(min_f) {
+        min_f.round() as u64 + 1
+    } else {
+        min_f.ceil() as u64
+    };
+
+    let max = if is_whole(max_f) {
+        max_f.round() as u64 - 1
+    } else {
+        max_f.floor() as u64
+    };
+
+    debug!(
+        time = race.time,
+        distance = race.distance,
+        min,
+        max,
+        "solved race"
+    );
+
+    max - min + 1
+}
+
+pub fn winning_possibilities_product(races: &[Race]) -> u64 {
+    races
+        .iter()
+        .fold(1, |acc, race| acc * winning_possibilities(race))
+}
+
+pub fn parse(lines: &[&str]) -> Result<Races> {
+    let (races, big_race) = construct_races(lines)?;
+    Ok(Races { races, big_race })
+}
+
+pub fn part_one(races: &Races) -> u64 {
+    winning_possibilities_product(&races.races)
+}
+
+pub fn part_two(races: &Races) -> u64 {
+    winning_possibilities(&races.big_race)
+}
+
+pub struct WaitForIt;
+
+impl Solution for WaitForIt {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?).into())
+    }
+
+    fn part_two(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?).into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "race duration",
+            base_size: 1024,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [false, false],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 6,
+    title: "Wait For It",
+    examples: &EXAMPLES,
+    input: Normalization::TRIM,
+    solution: &WaitForIt,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "
+    Time:      7  15   30
+    Distance:  9  40  200
+    ";
+
+    fn races() -> (Vec<Race>, Race) {
+        let lines = TEST_INPUT.split("\n").collect::<Vec<_>>();
+        construct_races(&lines).unwrap()
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(winning_possibilities_product(&races().0), 288)
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(winning_possibilities(&races().1), 71503)
+    }
+}
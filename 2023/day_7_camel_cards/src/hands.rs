@@ -1,11 +1,14 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ptr};
 
 use anyhow::{anyhow, Result};
+use aoc_common::puzzle::chosen;
+use serde::Serialize;
+use tracing::trace;
 
 use crate::parsing::get_hand_components;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-enum HandType {
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize)]
+pub enum HandType {
     HighCard,
     OnePair,
     TwoPair,
@@ -15,8 +18,22 @@ enum HandType {
     FiveOfAKind,
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-enum Card {
+impl HandType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandType::HighCard => "high card",
+            HandType::OnePair => "one pair",
+            HandType::TwoPair => "two pair",
+            HandType::ThreeOfAKind => "three of a kind",
+            HandType::FullHouse => "full house",
+            HandType::FourOfAKind => "four of a kind",
+            HandType::FiveOfAKind => "five of a kind",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Serialize)]
+pub enum Card {
     Joker,
     Number(u32),
     Jack,
@@ -26,7 +43,7 @@ enum Card {
 }
 
 impl Card {
-    fn new(c: char, use_joker: bool) -> Option<Self> {
+    pub fn new(c: char, use_joker: bool) -> Option<Self> {
         match c {
             '0'..='9' => Some(Card::Number(c.to_digit(10).unwrap())),
             'T' => Some(Card::Number(10)),
@@ -37,13 +54,46 @@ impl Card {
             _ => None,
         }
     }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Card::Number(10) => 'T',
+            Card::Number(n) => char::from_digit(*n, 10).unwrap_or('?'),
+            Card::Jack | Card::Joker => 'J',
+            Card::Queen => 'Q',
+            Card::King => 'K',
+            Card::Ace => 'A',
+        }
+    }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-struct Hand {
-    hand_type: HandType,
-    cards: Vec<Card>,
-    bid: u64,
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Serialize)]
+pub struct Hand {
+    pub hand_type: HandType,
+    pub cards: Vec<Card>,
+    pub bid: u64,
+}
+
+impl Hand {
+    pub fn with_jokers(&self) -> Hand {
+        let cards: Vec<_> = self
+            .cards
+            .iter()
+            .map(|&card| {
+                if card == Card::Jack {
+                    Card::Joker
+                } else {
+                    card
+                }
+            })
+            .collect();
+
+        Hand {
+            hand_type: get_hand_type(&cards),
+            cards,
+            bid: self.bid,
+        }
+    }
 }
 
 fn get_hand_type(cards: &[Card]) -> HandType {
@@ -79,7 +129,7 @@ fn get_hand_type(cards: &[Card]) -> HandType {
     }
 }
 
-fn construct_hands(lines: &[String], use_joker: bool) -> Result<Vec<Hand>> {
+pub fn construct_hands(lines: &[&str], use_joker: bool) -> Result<Vec<Hand>> {
     let filtered_lines: Vec<&str> = lines
         .iter()
         .map(|s| s.trim())
@@ -97,8 +147,11 @@ fn construct_hands(lines: &[String], use_joker: bool) -> Result<Vec<Hand>> {
                     .filter_map(|c| Card::new(*c, use_joker))
                     .collect();
 
+                let hand_type = get_hand_type(&cards);
+                trace!(?cards, ?hand_type, bid, "constructed hand");
+
                 Ok(Hand {
-                    hand_type: get_hand_type(&cards),
+                    hand_type,
                     cards,
                     bid,
                 })
@@ -109,30 +162,73 @@ fn construct_hands(lines: &[String], use_joker: bool) -> Result<Vec<Hand>> {
         .collect()
 }
 
-pub fn part_one_total_winnings(lines: &[String]) -> Result<u64> {
-    let mut hands = construct_hands(lines, false)?;
+pub fn total_winnings(hands: &[Hand]) -> u64 {
+    let mut hands = hands.to_vec();
     hands.sort();
 
-    let sum = hands
+    hands
         .iter()
         .enumerate()
         .map(|(i, hand)| (i as u64 + 1) * hand.bid)
-        .sum();
-
-    Ok(sum)
+        .sum()
 }
 
-pub fn part_two_total_winnings(lines: &[String]) -> Result<u64> {
-    let mut hands = construct_hands(lines, true)?;
-    hands.sort();
+/// Each hand's type and rank and what it wins, strongest hand last. Part two
+/// plays J as a joker.
+pub fn explain(hands: &[Hand], part: u8, item: Option<usize>) -> Result<String> {
+    let hands: Vec<_> = match part {
+        1 => hands.to_vec(),
+        _ => hands.iter().map(Hand::with_jokers).collect(),
+    };
+    let mut ranked: Vec<_> = hands.iter().collect();
+    ranked.sort();
 
-    let sum = hands
-        .iter()
-        .enumerate()
-        .map(|(i, hand)| (i as u64 + 1) * hand.bid)
-        .sum();
+    let mut steps = vec![];
+    for (n, hand) in chosen(&hands, item, "hands")? {
+        let rank = ranked
+            .iter()
+            .position(|&other| ptr::eq(other, hand))
+            .unwrap()
+            + 1;
+        let cards: String = hand.cards.iter().map(Card::symbol).collect();
+        let jokers = match hand
+            .cards
+            .iter()
+            .filter(|&&card| card == Card::Joker)
+            .count()
+        {
+            0 => String::new(),
+            1 => " with a joker".to_string(),
+            count => format!(" with {count} jokers"),
+        };
+
+        steps.push((
+            rank,
+            format!(
+                "Hand {n} {cards}: {}{jokers}, rank {rank} of {}, wins {rank} × {} = {}",
+                hand.hand_type.name(),
+                hands.len(),
+                hand.bid,
+                rank as u64 * hand.bid
+            ),
+        ));
+    }
+    steps.sort();
+
+    let mut steps: Vec<_> = steps.into_iter().map(|(_, step)| step).collect();
+    if item.is_none() {
+        steps.push(format!("Total winnings are {}", total_winnings(&hands)));
+    }
+
+    Ok(steps.join("\n"))
+}
+
+pub fn part_one_total_winnings(lines: &[&str]) -> Result<u64> {
+    Ok(total_winnings(&construct_hands(lines, false)?))
+}
 
-    Ok(sum)
+pub fn part_two_total_winnings(lines: &[&str]) -> Result<u64> {
+    Ok(total_winnings(&construct_hands(lines, true)?))
 }
 
 #[cfg(test)]
@@ -147,8 +243,8 @@ mod tests {
     QQQJA 483
     ";
 
-    fn lines() -> Vec<String> {
-        TEST_INPUT.split("\n").map(|s| s.to_string()).collect()
+    fn lines() -> Vec<&'static str> {
+        TEST_INPUT.split("\n").collect()
     }
 
     #[test]
@@ -160,4 +256,27 @@ mod tests {
     fn test_part_two() {
         assert_eq!(part_two_total_winnings(&lines()).unwrap(), 5905)
     }
+
+    #[test]
+    fn test_explain_ranks() {
+        let hands = construct_hands(&lines(), false).unwrap();
+
+        assert_eq!(
+            explain(&hands, 1, Some(1)).unwrap(),
+            "Hand 1 32T3K: one pair, rank 1 of 5, wins 1 × 765 = 765"
+        );
+        assert_eq!(
+            explain(&hands, 2, Some(4)).unwrap(),
+            "Hand 4 KTJJT: four of a kind with 2 jokers, rank 5 of 5, wins 5 × 220 = 1100"
+        );
+        assert!(explain(&hands, 2, None).unwrap().ends_with("are 5905"));
+    }
+
+    #[test]
+    fn test_with_jokers() {
+        let hands = construct_hands(&lines(), false).unwrap();
+        let joker_hands: Vec<_> = hands.iter().map(Hand::with_jokers).collect();
+
+        assert_eq!(joker_hands, construct_hands(&lines(), true).unwrap())
+    }
 }
\ No newline at end of file
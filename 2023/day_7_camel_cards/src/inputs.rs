@@ -0,0 +1,23 @@
+use aoc_common::generate::{same_length_number, shuffled_lines, Rng};
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let hand: String = (0..5).map(|_| rng.pick(CARDS) as char).collect();
+            format!("{hand} {}\n", rng.range(1, 1000))
+        })
+        .collect()
+}
+
+// Hands are kept, as their strength depends on the real card labels, but
+// their order and bids are not.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    shuffled_lines(input, rng, |line, rng| match line.split_once(' ') {
+        Some((hand, bid)) if bid.chars().all(|c| c.is_ascii_digit()) => {
+            format!("{hand} {}", same_length_number(bid, rng))
+        }
+        _ => line.to_string(),
+    })
+}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use serde_json::Value;
+
+pub mod hands;
+pub mod inputs;
+pub mod parsing;
+use hands::{construct_hands, total_winnings};
+pub use hands::{Card, Hand, HandType};
+
+pub const EXAMPLES: [&str; 1] = ["\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483"];
+
+pub fn parse(lines: &[&str]) -> Result<Vec<Hand>> {
+    construct_hands(lines, false)
+}
+
+pub fn part_one(hands: &[Hand]) -> u64 {
+    total_winnings(hands)
+}
+
+pub fn part_two(hands: &[Hand]) -> u64 {
+    let joker_hands: Vec<_> = hands.iter().map(Hand::with_jokers).collect();
+    total_winnings(&joker_hands)
+}
+
+pub struct CamelCards;
+
+impl Solution for CamelCards {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?).into())
+    }
+
+    fn part_two(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?).into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn explain(&self, lines: &[&str], part: u8, item: Option<usize>) -> Result<String> {
+        hands::explain(&parse(lines)?, part, item)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "hands",
+            base_size: 128,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [false, false],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 7,
+    title: "Camel Cards",
+    examples: &EXAMPLES,
+    input: Normalization::TRIM,
+    solution: &CamelCards,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
@@ -1,35 +1,17 @@
-use std::{
-    env,
-    fs::File,
-    io::{prelude::*, BufReader},
-};
-
 use anyhow::Result;
 
-pub mod hands;
-pub mod parsing;
-use hands::{part_one_total_winnings, part_two_total_winnings};
-
-fn lines_from_file(filename: &str) -> Vec<String> {
-    let file = File::open(filename).expect("Something went wrong reading the file");
-
-    let buf = BufReader::new(file);
-
-    buf.lines()
-        .map(|l| l.expect("Could not parse line").trim().to_string())
-        .collect()
-}
+use aoc_common::cli::Cli;
+use day_7_camel_cards::{parse, part_one, part_two, EXAMPLES, PUZZLE};
 
 fn main() -> Result<()> {
-    let filename = env::args()
-        .skip(1)
-        .next()
-        .expect("A filename must be passed as an argument.");
-
-    let file_lines = lines_from_file(&filename);
+    let cli = Cli::from_env(&PUZZLE);
+    let input = cli.input(&EXAMPLES)?;
+    let lines = cli.lines(&input);
+    let mut report = cli.report();
 
-    println!("Part 1: {}", part_one_total_winnings(&file_lines)?);
-    println!("Part 2: {}", part_two_total_winnings(&file_lines)?);
+    let hands = report.parse(|| parse(&lines))?;
+    report.part_one(|| Ok(part_one(&hands)))?;
+    report.part_two(|| Ok(part_two(&hands)))?;
 
-    Ok(())
-}
\ No newline at end of file
+    report.finish()
+}
@@ -0,0 +1,38 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc_common::input;
+use day_8_haunted_wasteland::{parse, PUZZLE};
+
+fn name(i: usize) -> String {
+    let letter = |n: usize| char::from(b'A' + (n % 26) as u8);
+    [letter(i / 676), letter(i / 26), letter(i)]
+        .iter()
+        .collect()
+}
+
+/// Every three letter node, each leading to the next two.
+fn network() -> String {
+    let nodes = 26 * 26 * 26;
+    let mut input = "LR".repeat(150) + "\n\n";
+
+    for i in 0..nodes {
+        let (left, right) = (name((i + 1) % nodes), name((i + 2) % nodes));
+        input += &format!("{} = ({left}, {right})\n", name(i));
+    }
+
+    input
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let input = network();
+
+    c.bench_function("day 8 parse", |b| {
+        b.iter(|| {
+            let lines = input::lines(black_box(&input), PUZZLE.input);
+            parse(&lines).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::generate::Rng;
+
+// Node names are drawn from letters other than 'A' and 'Z' so that only the
+// start and end nodes are picked up by the ghost walk in part two.
+const NODE_LETTERS: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY";
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let node_name = |i: usize| -> String {
+        (0..5)
+            .map(|place| {
+                let letter = i / NODE_LETTERS.len().pow(place) % NODE_LETTERS.len();
+                NODE_LETTERS[letter] as char
+            })
+            .collect()
+    };
+
+    let instructions: String = (0..rng.range(2, 300))
+        .map(|_| if rng.below(2) == 0 { 'L' } else { 'R' })
+        .collect();
+
+    let mut names = vec!["AAA".to_string()];
+    names.extend((0..size).map(node_name));
+    names.push("ZZZ".to_string());
+
+    let nodes: String = names
+        .windows(2)
+        .map(|pair| format!("{} = ({}, {})\n", pair[0], pair[1], pair[1]))
+        .collect();
+
+    format!("{instructions}\n\n{nodes}ZZZ = (ZZZ, ZZZ)\n")
+}
+
+// Nodes are renamed consistently. `AAA` stays the start, and names keep
+// ending in `A` or `Z` when they did, so both walks are unchanged.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    let mut lines = input.lines();
+    let Some(instructions) = lines.next() else {
+        return String::new();
+    };
+
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut taken: HashSet<String> = HashSet::from(["AAA".to_string()]);
+
+    let mut rename = |name: &str, rng: &mut Rng| -> String {
+        if name == "AAA" {
+            return name.to_string();
+        }
+        if let Some(renamed) = names.get(name) {
+            return renamed.clone();
+        }
+
+        let last = match name.chars().last() {
+            Some(c @ ('A' | 'Z')) => c,
+            _ => rng.pick(NODE_LETTERS) as char,
+        };
+        let renamed = loop {
+            let candidate: String = (1..name.len())
+                .map(|_| rng.pick(NODE_LETTERS) as char)
+                .chain([last])
+                .collect();
+            if taken.insert(candidate.clone()) {
+                break candidate;
+            }
+        };
+
+        names.insert(name.to_string(), renamed.clone());
+        renamed
+    };
+
+    let mut nodes = vec![];
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        let mut renamed = String::new();
+        let mut word = String::new();
+
+        for c in line.chars().chain(['\n']) {
+            if c.is_ascii_alphanumeric() {
+                word.push(c);
+                continue;
+            }
+            if !word.is_empty() {
+                renamed += &rename(&word, rng);
+                word.clear();
+            }
+            renamed.push(c);
+        }
+
+        nodes.push(renamed);
+    }
+    rng.shuffle(&mut nodes);
+
+    format!("{instructions}\n\n{}", nodes.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nodes_are_renamed_consistently() {
+        let input =
+            "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)\n";
+        let anonymized = anonymize(input, &mut Rng::new(7));
+        let nodes: Vec<&str> = anonymized.lines().skip(2).collect();
+
+        assert_eq!(nodes.len(), 4);
+        assert!(!anonymized.contains("11B") && !anonymized.contains("XXX"));
+        assert_eq!(
+            nodes.iter().filter(|node| node[..3].ends_with('A')).count(),
+            1
+        );
+        assert_eq!(
+            nodes.iter().filter(|node| node[..3].ends_with('Z')).count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use serde_json::Value;
+
+pub mod inputs;
+pub mod maps;
+pub mod parsing;
+use maps::{construct_map, part_one_total_steps, part_two_total_steps};
+pub use maps::{Direction, Map, Network, Node};
+
+pub const EXAMPLES: [&str; 3] = [
+    "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)",
+    "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)",
+    "\
+LR
+
+AAA = (AAB, XXX)
+AAB = (XXX, AAZ)
+AAZ = (AAB, XXX)
+BBA = (BBB, XXX)
+BBB = (BBC, BBC)
+BBC = (BBZ, BBZ)
+BBZ = (BBB, BBB)
+XXX = (XXX, XXX)",
+];
+
+pub fn parse<'a>(lines: &[&'a str]) -> Result<Map<'a>> {
+    construct_map(lines)
+}
+
+pub fn part_one(map: &Map, cancel: &CancelToken) -> Result<usize> {
+    part_one_total_steps(map, cancel)
+}
+
+pub fn part_two(map: &Map, cancel: &CancelToken) -> Result<usize> {
+    part_two_total_steps(map, cancel)
+}
+
+pub struct HauntedWasteland;
+
+impl Solution for HauntedWasteland {
+    fn part_one(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?, cancel)?.into())
+    }
+
+    fn part_two(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?, cancel)?.into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "nodes",
+            base_size: 256,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [true, true],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 8,
+    title: "Haunted Wasteland",
+    examples: &EXAMPLES,
+    input: Normalization::TRIM,
+    solution: &HauntedWasteland,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
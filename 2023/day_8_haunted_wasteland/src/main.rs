@@ -1,36 +1,18 @@
-use std::{
-    env,
-    fs::File,
-    io::{prelude::*, BufReader},
-};
-
 use anyhow::Result;
 
-pub mod maps;
-pub mod parsing;
-use maps::{construct_map, part_one_total_steps, part_two_total_steps};
-
-fn lines_from_file(filename: &str) -> Vec<String> {
-    let file = File::open(filename).expect("Something went wrong reading the file");
-
-    let buf = BufReader::new(file);
-
-    buf.lines()
-        .map(|l| l.expect("Could not parse line").trim().to_string())
-        .collect()
-}
+use aoc_common::{cancel::CancelToken, cli::Cli};
+use day_8_haunted_wasteland::{parse, part_one, part_two, EXAMPLES, PUZZLE};
 
 fn main() -> Result<()> {
-    let filename = env::args()
-        .skip(1)
-        .next()
-        .expect("A filename must be passed as an argument.");
-
-    let file_lines = lines_from_file(&filename);
-    let map = construct_map(&file_lines)?;
+    let cli = Cli::from_env(&PUZZLE);
+    let input = cli.input(&EXAMPLES)?;
+    let lines = cli.lines(&input);
+    let mut report = cli.report();
 
-    println!("Part 1: {}", part_one_total_steps(&map)?);
-    println!("Part 2: {}", part_two_total_steps(&map)?);
+    let map = report.parse(|| parse(&lines))?;
+    let cancel = CancelToken::new();
+    report.part_one(|| part_one(&map, &cancel))?;
+    report.part_two(|| part_two(&map, &cancel))?;
 
-    Ok(())
+    report.finish()
 }
@@ -1,18 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use anyhow::{anyhow, Ok, Result};
+use aoc_common::cancel::CancelToken;
 use num::integer::lcm;
+use serde::{Serialize, Serializer};
+use tracing::debug;
 
 use crate::parsing::get_node_components;
 
-type Network = HashMap<String, Node>;
+/// Nodes by name, with every name borrowed from the input.
+pub type Network<'a> = HashMap<&'a str, Node<'a>>;
 
-struct Node {
-    left: String,
-    right: String,
+#[derive(Debug, Serialize)]
+pub struct Node<'a> {
+    pub left: &'a str,
+    pub right: &'a str,
 }
 
-enum Direction {
+#[derive(Debug, Serialize)]
+pub enum Direction {
     Left,
     Right,
 }
@@ -28,14 +34,16 @@ fn instructions_to_directions(instructions: &str) -> Result<Vec<Direction>> {
         .collect()
 }
 
-pub fn construct_map(lines: &[String]) -> Result<Map> {
-    let filtered_lines: Vec<&str> = lines
+pub fn construct_map<'a>(lines: &[&'a str]) -> Result<Map<'a>> {
+    let filtered_lines: Vec<&'a str> = lines
         .iter()
         .map(|s| s.trim())
         .filter(|s| !s.is_empty())
         .collect();
 
-    let instructions = filtered_lines[0];
+    let instructions = filtered_lines
+        .first()
+        .ok_or_else(|| anyhow!("no instructions line"))?;
     let directions = instructions_to_directions(instructions)?;
 
     let network_result: Result<Network> = filtered_lines[1..]
@@ -43,13 +51,7 @@ pub fn construct_map(lines: &[String]) -> Result<Map> {
         .map(|&(mut line)| {
             let (node_id, (left, right)) = get_node_components(&mut line)?;
 
-            Ok((
-                node_id.to_string(),
-                Node {
-                    left: left.to_string(),
-                    right: right.to_string(),
-                },
-            ))
+            Ok((node_id, Node { left, right }))
         })
         .collect();
 
@@ -61,35 +63,50 @@ pub fn construct_map(lines: &[String]) -> Result<Map> {
     })
 }
 
-pub struct Map {
-    directions: Vec<Direction>,
-    network: Network,
+#[derive(Debug, Serialize)]
+pub struct Map<'a> {
+    pub directions: Vec<Direction>,
+    #[serde(serialize_with = "sorted_network")]
+    pub network: Network<'a>,
 }
 
-impl Map {
-    fn next_node<'s>(&'s self, node_id: &str, step: usize) -> Result<&'s str> {
+// Nodes are dumped by name so that two dumps of an input can be diffed.
+fn sorted_network<S: Serializer>(network: &Network, serializer: S) -> Result<S::Ok, S::Error> {
+    network
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
+impl<'a> Map<'a> {
+    fn next_node(&self, node_id: &str, step: usize) -> Result<&'a str> {
         let direction = &self.directions[step % self.directions.len()];
 
         let current_node = self
             .network
             .get(node_id)
-            .ok_or(anyhow!("Could not find node {node_id} in network"))?;
+            .ok_or_else(|| anyhow!("Could not find node {node_id} in network"))?;
 
         Ok(match direction {
-            Direction::Left => &current_node.left,
-            Direction::Right => &current_node.right,
+            Direction::Left => current_node.left,
+            Direction::Right => current_node.right,
         })
     }
 
-    fn steps_to_end(&self, start: &str) -> Result<usize> {
+    // Loops forever when no node ending in Z can be reached, unless cancelled.
+    fn steps_to_end(&self, start: &str, cancel: &CancelToken) -> Result<usize> {
         let mut steps = 0;
         let mut current_node_id = start;
+        let mut progress = cancel.progress(start, None);
 
         while !ends_in(current_node_id, 'Z') {
+            progress.tick()?;
             current_node_id = self.next_node(current_node_id, steps)?;
             steps += 1;
         }
 
+        debug!(start, end = current_node_id, steps, "reached end");
+
         Ok(steps)
     }
 }
@@ -98,16 +115,16 @@ fn ends_in(node_id: &str, ending_char: char) -> bool {
     node_id.chars().last().unwrap_or(' ') == ending_char
 }
 
-pub fn part_one_total_steps(map: &Map) -> Result<usize> {
-    map.steps_to_end("AAA")
+pub fn part_one_total_steps(map: &Map, cancel: &CancelToken) -> Result<usize> {
+    map.steps_to_end("AAA", cancel)
 }
 
-pub fn part_two_total_steps(map: &Map) -> Result<usize> {
+pub fn part_two_total_steps(map: &Map, cancel: &CancelToken) -> Result<usize> {
     let route_steps = map
         .network
         .keys()
         .filter(|node_id| ends_in(node_id, 'A'))
-        .map(|node_id| map.steps_to_end(&node_id))
+        .map(|node_id| map.steps_to_end(node_id, cancel))
         .collect::<Result<Vec<usize>>>()?;
 
     Ok(route_steps.iter().fold(1, |acc, &steps| lcm(acc, steps)))
@@ -150,16 +167,16 @@ mod tests {
     XXX = (XXX, XXX)
     ";
 
-    fn lines_1() -> Vec<String> {
-        TEST_INPUT_1.split("\n").map(|s| s.to_string()).collect()
+    fn lines_1() -> Vec<&'static str> {
+        TEST_INPUT_1.split("\n").collect()
     }
 
-    fn lines_2() -> Vec<String> {
-        TEST_INPUT_2.split("\n").map(|s| s.to_string()).collect()
+    fn lines_2() -> Vec<&'static str> {
+        TEST_INPUT_2.split("\n").collect()
     }
 
-    fn lines_3() -> Vec<String> {
-        TEST_INPUT_3.split("\n").map(|s| s.to_string()).collect()
+    fn lines_3() -> Vec<&'static str> {
+        TEST_INPUT_3.split("\n").collect()
     }
 
     #[test]
@@ -167,14 +184,29 @@ mod tests {
         let map_1 = construct_map(&lines_1()).unwrap();
         let map_2 = construct_map(&lines_2()).unwrap();
 
-        assert_eq!(part_one_total_steps(&map_1).unwrap(), 2);
-        assert_eq!(part_one_total_steps(&map_2).unwrap(), 6);
+        let cancel = CancelToken::new();
+
+        assert_eq!(part_one_total_steps(&map_1, &cancel).unwrap(), 2);
+        assert_eq!(part_one_total_steps(&map_2, &cancel).unwrap(), 6);
     }
 
     #[test]
     fn test_part_two() {
         let map_3 = construct_map(&lines_3()).unwrap();
 
-        assert_eq!(part_two_total_steps(&map_3).unwrap(), 6);
+        assert_eq!(
+            part_two_total_steps(&map_3, &CancelToken::new()).unwrap(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_unreachable_end_is_cancelled() {
+        let lines = ["L", "", "AAA = (BBB, BBB)", "BBB = (AAA, AAA)"];
+        let map = construct_map(&lines).unwrap();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+
+        assert!(part_one_total_steps(&map, &cancel).is_err());
     }
 }
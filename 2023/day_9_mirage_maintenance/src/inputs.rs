@@ -0,0 +1,39 @@
+use aoc_common::generate::{shuffled_lines, Rng};
+
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let coefficients: Vec<i64> = (0..rng.range(1, 6))
+                .map(|_| rng.range(0, 20) as i64 - 10)
+                .collect();
+
+            let values: Vec<String> = (0..21_i64)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, coefficient| acc * x + coefficient)
+                        .to_string()
+                })
+                .collect();
+
+            values.join(" ") + "\n"
+        })
+        .collect()
+}
+
+// Adding a constant to a history adds it to both extrapolated values.
+pub fn anonymize(input: &str, rng: &mut Rng) -> String {
+    shuffled_lines(input, rng, |line, rng| {
+        let constant = rng.range(0, 2_000) as i64 - 1_000;
+
+        line.split_whitespace()
+            .map(|value| {
+                value
+                    .parse::<i64>()
+                    .map_or(value.to_string(), |n| (n + constant).to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use aoc_common::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    puzzle::{Puzzle, Solution},
+};
+use serde_json::Value;
+
+pub mod inputs;
+pub mod oasis;
+pub mod parsing;
+pub use oasis::History;
+use oasis::{construct_histories, part_one_history_sum, part_two_history_sum};
+
+pub const EXAMPLES: [&str; 1] = ["\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45"];
+
+pub fn parse(lines: &[&str]) -> Result<Vec<History>> {
+    construct_histories(lines)
+}
+
+pub fn part_one(histories: &[History]) -> i64 {
+    part_one_history_sum(histories)
+}
+
+pub fn part_two(histories: &[History]) -> i64 {
+    part_two_history_sum(histories)
+}
+
+pub struct MirageMaintenance;
+
+impl Solution for MirageMaintenance {
+    fn part_one(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_one(&parse(lines)?).into())
+    }
+
+    fn part_two(&self, lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        Ok(part_two(&parse(lines)?).into())
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        Ok(serde_json::to_value(parse(lines)?)?)
+    }
+
+    fn explain(&self, lines: &[&str], part: u8, item: Option<usize>) -> Result<String> {
+        oasis::explain(&parse(lines)?, part, item)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(Generator {
+            unit: "histories",
+            base_size: 64,
+            generate: inputs::generate,
+        })
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        Some(Anonymizer {
+            preserves: [false, false],
+            anonymize: inputs::anonymize,
+        })
+    }
+}
+
+pub const PUZZLE: Puzzle = Puzzle {
+    year: 2023,
+    day: 9,
+    title: "Mirage Maintenance",
+    examples: &EXAMPLES,
+    input: Normalization::TRIM,
+    solution: &MirageMaintenance,
+};
+
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(PUZZLE);
@@ -1,10 +1,13 @@
 use anyhow::{anyhow, Ok, Result};
+use aoc_common::puzzle::chosen;
+use serde::Serialize;
+use tracing::trace;
 
 use crate::parsing::get_number_line;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct History {
-    analysis: Vec<Vec<i64>>,
+    pub analysis: Vec<Vec<i64>>,
 }
 
 impl History {
@@ -27,33 +30,104 @@ impl History {
                 .map(|window| window[1] - window[0])
                 .collect::<Vec<i64>>();
 
-            if next_analysis.len() < 1 {
+            if next_analysis.is_empty() {
                 analysis.push(vec![0]);
             } else {
                 analysis.push(next_analysis);
             }
         }
 
+        trace!(values = ?analysis[0], depth = analysis.len(), "analysed history");
+
         Ok(History { analysis })
     }
 
-    fn predict(&self) -> i64 {
+    pub fn predict(&self) -> i64 {
         self.analysis
             .iter()
             .filter_map(|sub_analysis| sub_analysis.last())
             .sum()
     }
 
-    fn predict_back(&self) -> i64 {
+    pub fn predict_back(&self) -> i64 {
         self.analysis
             .iter()
             .rev()
             .filter_map(|sub_analysis| sub_analysis.first())
             .fold(0, |acc, n| n - acc)
     }
+
+    /// The analysis with each row extended by one value, at the end or, going
+    /// back, at the start. The first row's new value is the prediction.
+    pub fn extended(&self, back: bool) -> Vec<Vec<i64>> {
+        let mut rows = self.analysis.clone();
+        let mut below = 0;
+
+        for row in rows.iter_mut().rev() {
+            if back {
+                below = row.first().copied().unwrap_or(0) - below;
+                row.insert(0, below);
+            } else {
+                below += row.last().copied().unwrap_or(0);
+                row.push(below);
+            }
+        }
+
+        rows
+    }
+}
+
+/// Lays the rows out as a pyramid, each value between the two it is the
+/// difference of.
+fn pyramid(rows: &[Vec<i64>]) -> String {
+    let longest = rows
+        .iter()
+        .flatten()
+        .map(|n| n.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let width = (longest + 2) / 2 * 2;
+
+    rows.iter()
+        .enumerate()
+        .map(|(depth, row)| {
+            let values: String = row.iter().map(|n| format!("{n:>width$}")).collect();
+            format!("{}{values}", " ".repeat(depth * width / 2))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn explain(histories: &[History], part: u8, item: Option<usize>) -> Result<String> {
+    let back = part != 1;
+    let mut steps = vec![];
+
+    for (n, history) in chosen(histories, item, "histories")? {
+        let rows = history.extended(back);
+        let (direction, value) = match back {
+            false => ("forward", rows[0].last()),
+            true => ("back", rows[0].first()),
+        };
+
+        steps.push(format!(
+            "History {n} extrapolates {direction} to {}:\n{}",
+            value.copied().unwrap_or(0),
+            pyramid(&rows)
+        ));
+    }
+
+    if item.is_none() {
+        let total = match back {
+            false => part_one_history_sum(histories),
+            true => part_two_history_sum(histories),
+        };
+        steps.push(format!("The extrapolated values add up to {total}"));
+    }
+
+    Ok(steps.join("\n\n"))
 }
 
-pub fn construct_histories(lines: &[String]) -> Result<Vec<History>> {
+pub fn construct_histories(lines: &[&str]) -> Result<Vec<History>> {
     let filtered_lines: Vec<&str> = lines
         .iter()
         .map(|s| s.trim())
@@ -84,8 +158,8 @@ mod tests {
     10 13 16 21 30 45
     ";
 
-    fn lines() -> Vec<String> {
-        TEST_INPUT.split("\n").map(|s| s.to_string()).collect()
+    fn lines() -> Vec<&'static str> {
+        TEST_INPUT.split("\n").collect()
     }
 
     #[test]
@@ -99,4 +173,21 @@ mod tests {
         let histories = construct_histories(&lines()).unwrap();
         assert_eq!(part_two_history_sum(&histories), 2);
     }
+
+    #[test]
+    fn test_explain_pyramid() {
+        let histories = construct_histories(&lines()).unwrap();
+
+        assert_eq!(
+            explain(&histories, 1, Some(1)).unwrap(),
+            "History 1 extrapolates forward to 18:
+   0   3   6   9  12  15  18
+     3   3   3   3   3   3
+       0   0   0   0   0"
+        );
+        assert_eq!(
+            explain(&histories, 2, Some(3)).unwrap().lines().next(),
+            Some("History 3 extrapolates back to 5:")
+        );
+    }
 }
@@ -16,7 +16,7 @@ fn parse_number_line(input: &mut &str) -> PResult<Vec<i64>> {
     repeat(0.., terminated(parse_digits, multispace0)).parse_next(input)
 }
 
-pub fn get_number_line<'s>(input: &mut &'s str) -> Result<Vec<i64>> {
+pub fn get_number_line(input: &mut &str) -> Result<Vec<i64>> {
     parse_number_line
         .parse_next(input)
         .map_err(|e| anyhow!(e.to_string()))
@@ -0,0 +1,197 @@
+use std::{fs, path::Path, time::Duration};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use aoc_common::{
+    answer::Answer,
+    generate::{Anonymizer, Rng},
+    input,
+    puzzle::Puzzle,
+};
+
+use crate::run;
+
+fn solve(
+    puzzle: &'static Puzzle,
+    part: u8,
+    input: &str,
+    timeout: Option<Duration>,
+) -> Result<Answer> {
+    let lines = input::normalize(input, puzzle.input).lines;
+    Ok(run::solve_within(puzzle, part, &lines, timeout)?.answer)
+}
+
+/// The root cause of `error` with its data masked: anonymizing renames nodes
+/// and rewrites numbers, so every word without a lowercase letter becomes `#`.
+fn root_cause_shape(error: &anyhow::Error) -> String {
+    let cause = error.root_cause().to_string();
+    let mut shape = String::new();
+    let mut word = String::new();
+
+    for c in cause.chars().chain([' ']) {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            shape += if word.chars().any(|c| c.is_ascii_lowercase()) {
+                &word
+            } else {
+                "#"
+            };
+            word.clear();
+        }
+        shape.push(c);
+    }
+    shape.pop();
+
+    shape
+}
+
+/// Checks that the solver behaves the same way on both inputs: each part
+/// either succeeds on both or fails on both for the same reason, and preserved
+/// answers match. A part that runs past `timeout` fails. Only the anonymized
+/// input's answers and errors are reported.
+pub fn check(
+    puzzle: &'static Puzzle,
+    anonymizer: &Anonymizer,
+    original: &str,
+    anonymized: &str,
+    timeout: Option<Duration>,
+) -> Result<Vec<String>> {
+    let mut report = vec![];
+
+    for part in 1..=2 {
+        let preserved = anonymizer.preserves[part as usize - 1];
+
+        let line = match (
+            solve(puzzle, part, original, timeout),
+            solve(puzzle, part, anonymized, timeout),
+        ) {
+            (Ok(before), Ok(after)) if preserved && before != after => {
+                bail!("Part {part} should still be {before} but is {after}")
+            }
+            (Ok(_), Ok(after)) if preserved => format!("Part {part}: {after}, unchanged"),
+            (Ok(_), Ok(after)) => format!("Part {part}: {after}"),
+            (Err(before), Err(after)) if root_cause_shape(&before) != root_cause_shape(&after) => {
+                bail!("Part {part} fails for a different reason: {before:#}, now {after:#}")
+            }
+            (Err(_), Err(e)) => format!("Part {part}: fails on both, now with: {e:#}"),
+            (Ok(_), Err(e)) => bail!("Part {part} only fails on the anonymized input: {e:#}"),
+            (Err(e), Ok(_)) => bail!("Part {part} no longer fails: {e:#}"),
+        };
+        report.push(line);
+    }
+
+    Ok(report)
+}
+
+pub fn run(
+    puzzle: &'static Puzzle,
+    original: &str,
+    output: Option<&Path>,
+    seed: u64,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let anonymizer = puzzle.solution.anonymizer().ok_or(anyhow!(
+        "Day {} of {} has no anonymizer",
+        puzzle.day,
+        puzzle.year
+    ))?;
+
+    let anonymized = (anonymizer.anonymize)(original, &mut Rng::new(seed));
+
+    for line in check(puzzle, &anonymizer, original, &anonymized, timeout)? {
+        eprintln!("{line}");
+    }
+
+    match output {
+        Some(path) => fs::write(path, anonymized)
+            .with_context(|| format!("Could not write {}", path.display()))?,
+        None => print!("{anonymized}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_registry::years;
+
+    #[test]
+    fn test_examples_behave_the_same() {
+        for puzzle in &years()[&2023] {
+            let anonymizer = puzzle.solution.anonymizer().unwrap();
+
+            for (i, example) in puzzle.examples.iter().enumerate() {
+                let anonymized = (anonymizer.anonymize)(example, &mut Rng::new(i as u64 + 1));
+
+                assert!(
+                    check(puzzle, &anonymizer, example, &anonymized, None).is_ok(),
+                    "day {} example {}",
+                    puzzle.day,
+                    i + 1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors_survive_anonymizing() {
+        let puzzle = aoc_registry::find(2023, 8).unwrap();
+        let anonymizer = puzzle.solution.anonymizer().unwrap();
+        let broken = "LR\n\nAAA = (BBB, BBB)\nBBB = (CCC, CCC)\n";
+        let anonymized = (anonymizer.anonymize)(broken, &mut Rng::new(3));
+
+        let report = check(puzzle, &anonymizer, broken, &anonymized, None).unwrap();
+        assert!(report[0].contains("fails on both"));
+    }
+
+    #[test]
+    fn test_endless_parts_time_out() {
+        let puzzle = aoc_registry::find(2023, 8).unwrap();
+        let anonymizer = puzzle.solution.anonymizer().unwrap();
+        let endless = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n";
+        let anonymized = (anonymizer.anonymize)(endless, &mut Rng::new(5));
+        let timeout = Some(Duration::from_millis(50));
+
+        let report = check(puzzle, &anonymizer, endless, &anonymized, timeout).unwrap();
+        assert!(report[0].contains("fails on both, now with: timed out"));
+    }
+
+    #[test]
+    fn test_errors_must_have_the_same_cause() {
+        let puzzle = aoc_registry::find(2023, 3).unwrap();
+        let anonymizer = puzzle.solution.anonymizer().unwrap();
+        let ragged = "467..\n...*.\n..35\n";
+        let indented = "467..\n...*.\n ..35\n";
+
+        let error = check(puzzle, &anonymizer, ragged, indented, None).unwrap_err();
+        assert!(error.to_string().contains("fails for a different reason"));
+    }
+
+    #[test]
+    fn test_day_one_answers_are_preserved() {
+        let puzzle = aoc_registry::find(2023, 1).unwrap();
+        let anonymizer = puzzle.solution.anonymizer().unwrap();
+        let input = "\
+fivezg8jmf6hrxnhgxxttwoneg
+4nineeightseven2
+eighthree7sixkfgcxqsjtwo
+xtwone3four
+6fhlkrjqmqsevenbrpvkgfour9
+zoneight234
+3rd7oneightmj
+7pqrstsixteen
+";
+
+        for seed in 1..=5 {
+            let anonymized = (anonymizer.anonymize)(input, &mut Rng::new(seed));
+            assert_ne!(anonymized, input);
+
+            let report = check(puzzle, &anonymizer, input, &anonymized, None).unwrap();
+            assert!(report.iter().all(|line| line.ends_with("unchanged")));
+        }
+    }
+}
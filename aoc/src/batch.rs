@@ -0,0 +1,164 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+
+use aoc_common::{input, puzzle::Puzzle};
+
+use crate::{cache::Cache, run, table::Table};
+
+/// The files of `dir` that look like inputs, in name order. Hidden files and
+/// accepted answers are left out.
+pub fn input_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Could not read {}", dir.display()))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if entry.file_type()?.is_file() && !name.starts_with('.') && !name.ends_with(".answers") {
+            files.push(entry.path());
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// One row per file with both answers and the time they took. Files that
+/// cannot be read or solved are flagged in the last column instead of
+/// stopping the batch.
+pub fn batch(
+    puzzle: &'static Puzzle,
+    files: &[PathBuf],
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Table {
+    let rows = files
+        .iter()
+        .map(|path| {
+            let name = path.file_name().map_or(path.display().to_string(), |name| {
+                name.to_string_lossy().into_owned()
+            });
+
+            let input = match fs::read_to_string(path) {
+                Ok(input) => input,
+                Err(e) => {
+                    return vec![
+                        name,
+                        String::new(),
+                        String::new(),
+                        String::new(),
+                        e.to_string(),
+                    ]
+                }
+            };
+            let lines = input::lines(&input, puzzle.input);
+
+            let mut row = vec![name];
+            let mut elapsed = Duration::ZERO;
+            let mut problems = vec![];
+
+            for part in 1..=2 {
+                match run::solve(puzzle, part, &input, &lines, cache, timeout) {
+                    Ok(run::Part { solved, .. }) => {
+                        elapsed += Duration::from_secs_f64(solved.seconds);
+                        row.push(solved.answer.to_string());
+                    }
+                    Err(e) => {
+                        problems.push(format!("{:#}", e.root_cause()));
+                        row.push(
+                            if e.is::<run::TimedOut>() {
+                                "timed out"
+                            } else {
+                                "failed"
+                            }
+                            .to_string(),
+                        );
+                    }
+                }
+            }
+
+            let solved_any = problems.len() < 2;
+            row.push(if solved_any {
+                format!("{elapsed:.2?}")
+            } else {
+                String::new()
+            });
+            problems.dedup();
+            row.push(problems.join("; "));
+            row
+        })
+        .collect();
+
+    Table {
+        headers: vec!["File", "Part 1", "Part 2", "Time", "Problem"],
+        rows,
+    }
+}
+
+pub fn run(
+    puzzle: &'static Puzzle,
+    dir: &Path,
+    csv: bool,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let files = input_files(dir)?;
+    let table = batch(puzzle, &files, cache, timeout);
+
+    if csv {
+        print!("{}", table.to_csv());
+    } else {
+        println!("{}\n", puzzle.name());
+        print!("{}", table.to_text());
+
+        let flagged = table.rows.iter().filter(|row| !row[4].is_empty()).count();
+        if flagged > 0 {
+            println!("\n{flagged} of {} files could not be solved", files.len());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_files_skip_answers_and_hidden_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["bob.txt", "alice.txt", "alice.answers", ".notes"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        fs::create_dir(dir.path().join("old")).unwrap();
+
+        let names: Vec<PathBuf> = input_files(dir.path())
+            .unwrap()
+            .iter()
+            .map(|path| path.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(names, [Path::new("alice.txt"), Path::new("bob.txt")]);
+    }
+
+    #[test]
+    fn test_batch_flags_broken_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        fs::write(dir.path().join("example.txt"), puzzle.examples[0]).unwrap();
+        fs::write(dir.path().join("garbled.txt"), "Time: seven\nDistance: 9\n").unwrap();
+
+        let table = batch(puzzle, &input_files(dir.path()).unwrap(), None, None);
+
+        assert_eq!(table.rows[0][..3], ["example.txt", "288", "71503"]);
+        assert_eq!(table.rows[0][4], "");
+        assert_eq!(table.rows[1][..3], ["garbled.txt", "failed", "failed"]);
+        assert_eq!(table.rows[1][3], "");
+        assert!(!table.rows[1][4].is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use aoc_common::{answer::Answer, puzzle::Puzzle};
+
+pub const CACHE_DIR: &str = ".aoc-cache";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Solved {
+    pub answer: Answer,
+    pub seconds: f64,
+}
+
+/// Answers and timings on disk, one file per part, keyed by everything that
+/// could change them: the puzzle, the part, the solution's fingerprint and the
+/// input itself.
+#[derive(Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Cache {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn key(puzzle: &Puzzle, part: u8, input: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{} {} {part} ", puzzle.year, puzzle.day));
+        hasher.update(aoc_registry::fingerprint(puzzle));
+        hasher.update(Sha256::digest(input));
+
+        format!("{:x}", hasher.finalize())
+    }
+
+    pub fn get(&self, key: &str) -> Option<Solved> {
+        let path = self.path(key);
+        let contents = fs::read_to_string(&path).ok()?;
+
+        match serde_json::from_str(&contents) {
+            Ok(solved) => Some(solved),
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "ignoring unreadable cache entry");
+                None
+            }
+        }
+    }
+
+    pub fn put(&self, key: &str, solved: &Solved) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Could not create {}", self.dir.display()))?;
+
+        let path = self.path(key);
+        fs::write(&path, serde_json::to_string(solved)?)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        debug!(path = %path.display(), "cached answer");
+
+        Ok(())
+    }
+
+    /// Removes every cached answer, returning how many there were.
+    pub fn clear(&self) -> Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Could not read {}", self.dir.display()))
+            }
+        };
+
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Could not remove {}", path.display()))?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        Path::new(&self.dir).join(format!("{key}.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path().join("cache"));
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let key = Cache::key(puzzle, 1, "Time: 7\nDistance: 9\n");
+        let solved = Solved {
+            answer: Answer::from(4_u64),
+            seconds: 0.5,
+        };
+
+        assert_eq!(cache.get(&key), None);
+        cache.put(&key, &solved).unwrap();
+        assert_eq!(cache.get(&key), Some(solved));
+
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.clear().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_key_depends_on_part_and_input() {
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let key = Cache::key(puzzle, 1, "Time: 7");
+
+        assert_eq!(key, Cache::key(puzzle, 1, "Time: 7"));
+        assert_ne!(key, Cache::key(puzzle, 2, "Time: 7"));
+        assert_ne!(key, Cache::key(puzzle, 1, "Time: 8"));
+        assert_ne!(
+            key,
+            Cache::key(aoc_registry::find(2023, 7).unwrap(), 1, "Time: 7")
+        );
+    }
+
+    #[test]
+    fn test_unreadable_entry_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        fs::write(dir.path().join("broken.json"), "{").unwrap();
+
+        assert_eq!(cache.get("broken"), None);
+    }
+}
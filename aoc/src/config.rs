@@ -0,0 +1,285 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use aoc_common::cli::Format;
+
+use crate::{
+    cache::{Cache, CACHE_DIR},
+    history::{self, History, HISTORY_FILE},
+    inputs::{self, Store, KEY_FILE, SEALED_DIR},
+    run,
+};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+const SESSION_FILE: &str = ".aoc-session";
+const FIXTURES_DIR: &str = "fixtures";
+const REPETITIONS: usize = 3;
+
+/// `aoc.toml` as written. Every key is optional.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct File {
+    inputs: Option<PathBuf>,
+    fixtures: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    session: Option<PathBuf>,
+    plugins: Option<PathBuf>,
+    history: Option<PathBuf>,
+    format: Option<String>,
+    timeout: Option<f64>,
+    repetitions: Option<usize>,
+}
+
+/// Settings shared by the subcommands. Each comes from the first of the
+/// command line, an `AOC_*` environment variable, `aoc.toml` and a built-in
+/// default that sets it.
+#[derive(Debug)]
+pub struct Config {
+    /// Where `aoc.toml` was found, or the working directory without one.
+    /// Relative paths in the file, the cache, sealed inputs and the key are
+    /// all kept here.
+    pub root: PathBuf,
+    pub file: Option<PathBuf>,
+    pub inputs: PathBuf,
+    /// Inputs that are not ours, e.g. `fixtures/2023/day_05/` for a batch.
+    pub fixtures: PathBuf,
+    /// A single file of accepted answers, instead of `.answers` files beside
+    /// each input.
+    pub answers: Option<PathBuf>,
+    pub session: PathBuf,
+    /// Day libraries to load on top of the built-in days, only when set.
+    pub plugins: Option<PathBuf>,
+    /// SQLite database of recorded runs.
+    pub history: PathBuf,
+    pub format: Format,
+    /// Seconds a part may run, 0 for no limit.
+    pub timeout: f64,
+    /// Runs of each part when timing, of which the fastest counts.
+    pub repetitions: usize,
+}
+
+/// The nearest `aoc.toml` in `start` or one of its parents.
+pub fn find(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+fn parse_format(format: &str) -> Result<Format> {
+    Format::from_str(format, true).map_err(|_| anyhow!("{format} is not an output format"))
+}
+
+impl Config {
+    pub fn from_env(explicit: Option<&Path>) -> Result<Config> {
+        let cwd = env::current_dir()?;
+        let file = match explicit {
+            Some(path) => Some(cwd.join(path)),
+            None => find(&cwd),
+        };
+
+        Config::load(&cwd, file, |name| env::var(name).ok())
+    }
+
+    pub fn load(
+        cwd: &Path,
+        file: Option<PathBuf>,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Config> {
+        let (root, contents) = match &file {
+            Some(path) => {
+                let contents = fs::read_to_string(path)
+                    .with_context(|| format!("Could not read {}", path.display()))?;
+                let contents: File = toml::from_str(&contents)
+                    .with_context(|| format!("{} is not a valid config", path.display()))?;
+
+                (path.parent().unwrap_or(cwd).to_path_buf(), contents)
+            }
+            None => (cwd.to_path_buf(), File::default()),
+        };
+
+        let path = |name: &str, from_file: Option<PathBuf>| {
+            var(name)
+                .map(PathBuf::from)
+                .or(from_file.map(|path| root.join(path)))
+        };
+        let format = match var("AOC_FORMAT").or(contents.format) {
+            Some(format) => parse_format(&format)?,
+            None => Format::Text,
+        };
+        let timeout = match var("AOC_TIMEOUT") {
+            Some(value) => value
+                .parse()
+                .map_err(|_| anyhow!("AOC_TIMEOUT should be seconds, not {value}"))?,
+            None => contents.timeout.unwrap_or(run::DEFAULT_TIMEOUT_SECONDS),
+        };
+        let repetitions = match var("AOC_REPETITIONS") {
+            Some(value) => value
+                .parse()
+                .map_err(|_| anyhow!("AOC_REPETITIONS should be a count, not {value}"))?,
+            None => contents.repetitions.unwrap_or(REPETITIONS),
+        };
+
+        Ok(Config {
+            inputs: path("AOC_INPUTS", contents.inputs)
+                .unwrap_or_else(|| root.join(inputs::INPUTS_DIR)),
+            fixtures: path("AOC_FIXTURES", contents.fixtures)
+                .unwrap_or_else(|| root.join(FIXTURES_DIR)),
+            answers: path("AOC_ANSWERS", contents.answers),
+            session: path("AOC_SESSION_FILE", contents.session)
+                .unwrap_or_else(|| root.join(SESSION_FILE)),
+            plugins: path("AOC_PLUGINS", contents.plugins),
+            history: path("AOC_HISTORY", contents.history)
+                .unwrap_or_else(|| root.join(HISTORY_FILE)),
+            format,
+            timeout,
+            repetitions: repetitions.max(1),
+            root,
+            file,
+        })
+    }
+
+    pub fn store(&self, inputs: Option<PathBuf>) -> Result<Store> {
+        Store::open(
+            inputs.unwrap_or_else(|| self.inputs.clone()),
+            self.root.join(SEALED_DIR),
+            &self.root.join(KEY_FILE),
+            self.answers.clone(),
+        )
+    }
+
+    pub fn cache(&self, no_cache: bool) -> Option<Cache> {
+        (!no_cache).then(|| Cache::new(self.root.join(CACHE_DIR)))
+    }
+
+    /// The run history, recording at the commit checked out in the root.
+    pub fn history(&self) -> Result<History> {
+        History::open(&self.history, history::current_commit(&self.root))
+    }
+
+    pub fn key_file(&self) -> PathBuf {
+        self.root.join(KEY_FILE)
+    }
+
+    /// Whether a session token is stored, without ever printing it.
+    pub fn has_session(&self) -> bool {
+        fs::read_to_string(&self.session).is_ok_and(|token| !token.trim().is_empty())
+    }
+
+    pub fn print(&self) {
+        let file = self
+            .file
+            .as_ref()
+            .map_or("none, using defaults".to_string(), |file| {
+                file.display().to_string()
+            });
+        let answers = self.answers.as_ref().map_or(
+            "an .answers file beside each input".to_string(),
+            |answers| answers.display().to_string(),
+        );
+        let session = if self.has_session() { "" } else { " (missing)" };
+        let plugins = self
+            .plugins
+            .as_ref()
+            .map_or("none".to_string(), |plugins| plugins.display().to_string());
+        let timeout = match run::timeout(self.timeout) {
+            Some(timeout) => format!("{timeout:?}"),
+            None => "none".to_string(),
+        };
+
+        println!("config       {file}");
+        println!("inputs       {}", self.inputs.display());
+        println!("fixtures     {}", self.fixtures.display());
+        println!("answers      {answers}");
+        println!("session      {}{session}", self.session.display());
+        println!("plugins      {plugins}");
+        println!("history      {}", self.history.display());
+        println!("format       {:?}", self.format);
+        println!("timeout      {timeout}");
+        println!("repetitions  {}", self.repetitions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn no_vars(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn test_found_in_a_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("2023/day_5");
+        fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find(&nested), None);
+        fs::write(dir.path().join(CONFIG_FILE), "").unwrap();
+        assert_eq!(find(&nested), Some(dir.path().join(CONFIG_FILE)));
+    }
+
+    #[test]
+    fn test_defaults_and_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(CONFIG_FILE);
+        fs::write(
+            &file,
+            "inputs = \"private/inputs\"\ntimeout = 5.0\nformat = \"json\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(Path::new("/elsewhere"), Some(file), no_vars).unwrap();
+        assert_eq!(config.root, dir.path());
+        assert_eq!(config.inputs, dir.path().join("private/inputs"));
+        assert_eq!(config.fixtures, dir.path().join(FIXTURES_DIR));
+        assert_eq!(config.answers, None);
+        assert_eq!(config.plugins, None);
+        assert_eq!((config.format, config.timeout), (Format::Json, 5.0));
+        assert_eq!(config.repetitions, REPETITIONS);
+    }
+
+    #[test]
+    fn test_environment_overrides_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(CONFIG_FILE);
+        fs::write(&file, "timeout = 5.0\nrepetitions = 10\n").unwrap();
+        let vars = HashMap::from([
+            ("AOC_TIMEOUT", "0"),
+            ("AOC_INPUTS", "/shared/inputs"),
+            ("AOC_FORMAT", "JSON"),
+            ("AOC_PLUGINS", "/shared/plugins"),
+        ]);
+
+        let config = Config::load(dir.path(), Some(file), |name| {
+            vars.get(name).map(|value| value.to_string())
+        })
+        .unwrap();
+        assert_eq!(config.timeout, 0.0);
+        assert_eq!(config.inputs, Path::new("/shared/inputs"));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(
+            config.plugins.as_deref(),
+            Some(Path::new("/shared/plugins"))
+        );
+        assert_eq!(config.repetitions, 10);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(CONFIG_FILE);
+        fs::write(&file, "input = \"typo\"\n").unwrap();
+
+        assert!(Config::load(dir.path(), Some(file), no_vars).is_err());
+    }
+}
@@ -0,0 +1,707 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, PoisonError,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table, TableState, Wrap},
+    Frame, Terminal,
+};
+use tracing::{info, warn};
+use tracing_subscriber::fmt::MakeWriter;
+
+use aoc_common::{answer::Answer, input, logging, puzzle::Puzzle};
+
+use crate::{
+    cache::{Cache, Solved},
+    explain,
+    history::{History, Kind},
+    inputs::Store,
+    progress, run, scale,
+    verify::{self, Verdict},
+};
+
+// Only the end of the log is ever on screen.
+const LOG_LINES: usize = 1000;
+const TICK: Duration = Duration::from_millis(100);
+const HELP: &str = "↑↓ select  r run  R re-run without the cache  b benchmark  e explain  q quit";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Job {
+    Run,
+    Rerun,
+    Benchmark,
+}
+
+/// The last thing that came of solving a part.
+#[derive(Clone, Debug)]
+enum Outcome {
+    Solved {
+        answer: Answer,
+        seconds: f64,
+        /// How the time was taken, when not from a single fresh run.
+        note: String,
+    },
+    Failed(String),
+}
+
+struct Day {
+    puzzle: &'static Puzzle,
+    input: Option<String>,
+    expected: [Option<Answer>; 2],
+    parts: [Option<Outcome>; 2],
+    busy: Option<Job>,
+}
+
+impl Day {
+    fn verdicts(&self) -> [Option<Verdict>; 2] {
+        [0, 1].map(|index| match &self.parts[index] {
+            Some(Outcome::Solved { answer, .. }) => {
+                Some(verify::verdict(self.expected[index].as_ref(), answer))
+            }
+            _ => None,
+        })
+    }
+
+    fn stars(&self) -> usize {
+        self.verdicts()
+            .iter()
+            .filter(|verdict| matches!(verdict, Some(Verdict::Correct)))
+            .count()
+    }
+
+    fn row(&self) -> Row<'static> {
+        let answers = self.parts.clone().map(|part| match (self.busy, part) {
+            (Some(_), _) => "running…".to_string(),
+            (None, None) if self.input.is_none() => "no input".to_string(),
+            (None, None) => "–".to_string(),
+            (None, Some(Outcome::Solved { answer, .. })) => answer.to_string(),
+            (None, Some(Outcome::Failed(e))) => e,
+        });
+
+        let times: Vec<_> = self
+            .parts
+            .iter()
+            .flatten()
+            .filter_map(|part| match part {
+                Outcome::Solved { seconds, .. } => {
+                    Some(format!("{:.2?}", Duration::from_secs_f64(*seconds)))
+                }
+                Outcome::Failed(_) => None,
+            })
+            .collect();
+        let note = match &self.parts[0] {
+            Some(Outcome::Solved { note, .. }) if !note.is_empty() => format!(" ({note})"),
+            _ => String::new(),
+        };
+
+        let verdicts = self.verdicts().map(|verdict| match verdict {
+            Some(Verdict::Correct) => "ok",
+            Some(Verdict::Wrong { .. }) => "wrong",
+            Some(Verdict::Unknown) => "unknown",
+            None => "–",
+        });
+
+        Row::new([
+            self.puzzle.year.to_string(),
+            self.puzzle.day.to_string(),
+            self.puzzle.title.to_string(),
+            "★".repeat(self.stars()),
+            answers[0].clone(),
+            answers[1].clone(),
+            format!("{}{note}", times.join(" / ")),
+            verdicts.join(" / "),
+        ])
+    }
+}
+
+/// The end of the log, filled in by the tracing subscriber.
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<VecDeque<String>>>);
+
+impl Logs {
+    fn push(&self, line: &str) {
+        let mut lines = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+        lines.push_back(line.to_string());
+        if lines.len() > LOG_LINES {
+            lines.pop_front();
+        }
+    }
+
+    fn last(&self, count: usize) -> Vec<String> {
+        let lines = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        lines
+            .iter()
+            .skip(lines.len().saturating_sub(count))
+            .cloned()
+            .collect()
+    }
+}
+
+struct LogWriter {
+    logs: Logs,
+    pending: Vec<u8>,
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        for line in String::from_utf8_lossy(&self.pending).lines() {
+            self.logs.push(line);
+        }
+        self.pending.clear();
+
+        Ok(())
+    }
+}
+
+// The subscriber makes a writer per event and drops it once written.
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<'w> MakeWriter<'w> for Logs {
+    type Writer = LogWriter;
+
+    fn make_writer(&'w self) -> LogWriter {
+        LogWriter {
+            logs: self.clone(),
+            pending: vec![],
+        }
+    }
+}
+
+/// Does `job` for both parts, off the UI thread.
+fn work(
+    puzzle: &'static Puzzle,
+    input: &str,
+    job: Job,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+    repetitions: usize,
+) -> [Outcome; 2] {
+    let lines = input::lines(input, puzzle.input);
+
+    [1, 2].map(|part| {
+        let solved = match job {
+            Job::Run => run::solve(puzzle, part, input, &lines, cache, timeout),
+            // A fresh answer still replaces the cached one.
+            Job::Rerun | Job::Benchmark => run::solve(puzzle, part, input, &lines, None, timeout)
+                .and_then(|solved| {
+                    if let Some(cache) = cache {
+                        cache.put(&Cache::key(puzzle, part, input), &solved.solved)?;
+                    }
+                    Ok(solved)
+                }),
+        };
+
+        let run::Part { solved, cached } = match solved {
+            Ok(solved) => solved,
+            Err(e) => return Outcome::Failed(e.root_cause().to_string()),
+        };
+        // Every repetition gets the same time budget as a run.
+        let repeat =
+            || run::solve(puzzle, part, input, &lines, None, timeout).map(|part| part.solved);
+        let (seconds, note) = match job {
+            Job::Benchmark => match scale::time_part(repetitions, repeat) {
+                Ok(fastest) => (fastest.seconds, format!("best of {repetitions}")),
+                Err(e) => return Outcome::Failed(e.root_cause().to_string()),
+            },
+            _ if cached => (solved.seconds, "cached".to_string()),
+            _ => (solved.seconds, String::new()),
+        };
+
+        Outcome::Solved {
+            answer: solved.answer,
+            seconds,
+            note,
+        }
+    })
+}
+
+/// What a worker sends back once it is done, with the index of its day.
+enum Done {
+    Job(usize, [Outcome; 2]),
+    Explanation(usize, String),
+}
+
+struct App {
+    days: Vec<Day>,
+    selected: TableState,
+    /// The explanation on screen, and how far down it is scrolled.
+    explanation: Option<(String, u16)>,
+    /// The day whose explanation is still being worked out.
+    explaining: Option<usize>,
+    logs: Logs,
+    cache: Option<Cache>,
+    history: Option<History>,
+    timeout: Option<Duration>,
+    repetitions: usize,
+    sender: Sender<Done>,
+    receiver: Receiver<Done>,
+}
+
+impl App {
+    /// Every puzzle with its input and accepted answers, and whatever answers
+    /// are already in the cache.
+    fn new(
+        puzzles: Vec<&'static Puzzle>,
+        store: &Store,
+        cache: Option<Cache>,
+        history: Option<History>,
+        timeout: Option<Duration>,
+        repetitions: usize,
+        logs: Logs,
+    ) -> Result<App> {
+        let mut days = vec![];
+
+        for puzzle in puzzles {
+            let input = store.input(puzzle)?;
+            let parts = [1, 2].map(|part| {
+                let input = input.as_deref()?;
+                let Solved { answer, seconds } =
+                    cache.as_ref()?.get(&Cache::key(puzzle, part, input))?;
+
+                Some(Outcome::Solved {
+                    answer,
+                    seconds,
+                    note: "cached".to_string(),
+                })
+            });
+
+            days.push(Day {
+                puzzle,
+                input,
+                expected: verify::expected_answers(store, puzzle)?,
+                parts,
+                busy: None,
+            });
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        Ok(App {
+            days,
+            selected: TableState::default().with_selected(Some(0)),
+            explanation: None,
+            explaining: None,
+            logs,
+            cache,
+            history,
+            timeout,
+            repetitions,
+            sender,
+            receiver,
+        })
+    }
+
+    fn start(&mut self, job: Job) {
+        let Some(index) = self.selected.selected() else {
+            return;
+        };
+        let day = &mut self.days[index];
+        let (year, number) = (day.puzzle.year, day.puzzle.day);
+
+        let Some(input) = day.input.clone() else {
+            warn!(year, day = number, "no input to solve");
+            return;
+        };
+        if day.busy.is_some() {
+            warn!(year, day = number, "already running");
+            return;
+        }
+
+        info!(year, day = number, ?job, "started");
+        day.busy = Some(job);
+
+        let puzzle = day.puzzle;
+        let cache = self.cache.clone();
+        let (timeout, repetitions) = (self.timeout, self.repetitions);
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let outcomes = work(puzzle, &input, job, cache.as_ref(), timeout, repetitions);
+            // Nothing is listening once the dashboard has been closed.
+            let _ = sender.send(Done::Job(index, outcomes));
+        });
+    }
+
+    /// Takes in what workers have finished, recording the parts that were
+    /// solved rather than taken from the cache. An explanation is only shown
+    /// if it is still waited for.
+    fn collect(&mut self) {
+        while let Ok(done) = self.receiver.try_recv() {
+            let (index, outcomes) = match done {
+                Done::Job(index, outcomes) => (index, outcomes),
+                Done::Explanation(index, text) => {
+                    if self.explaining == Some(index) {
+                        self.explaining = None;
+                        self.explanation = Some((text, 0));
+                    }
+                    continue;
+                }
+            };
+            let day = &mut self.days[index];
+            info!(year = day.puzzle.year, day = day.puzzle.day, "finished");
+
+            let kind = match day.busy.take() {
+                Some(Job::Benchmark) => Kind::Benchmark,
+                _ => Kind::Run,
+            };
+            for (part, outcome) in (1..).zip(&outcomes) {
+                let (
+                    Some(history),
+                    Outcome::Solved {
+                        answer,
+                        seconds,
+                        note,
+                    },
+                ) = (&self.history, outcome)
+                else {
+                    continue;
+                };
+                if note == "cached" {
+                    continue;
+                }
+
+                let solved = Solved {
+                    answer: answer.clone(),
+                    seconds: *seconds,
+                };
+                let input = day.input.as_deref().unwrap_or_default();
+                if let Err(e) = history.record(day.puzzle, part, input, &solved, kind) {
+                    warn!(error = %format!("{e:#}"), "could not record the run");
+                }
+            }
+
+            day.parts = outcomes.map(Some);
+        }
+    }
+
+    /// Opens the explanation of the selected day, which is worked out off the
+    /// UI thread like any other job.
+    fn explain(&mut self) {
+        let Some(index) = self.selected.selected() else {
+            return;
+        };
+        let day = &self.days[index];
+
+        let Some(input) = day.input.clone() else {
+            self.explanation = Some(("There is no input to explain".to_string(), 0));
+            return;
+        };
+        self.explanation = Some(("Explaining…".to_string(), 0));
+        self.explaining = Some(index);
+
+        let puzzle = day.puzzle;
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let text = match explain::explanations(puzzle, &input, None) {
+                Ok(explanations) => (1..)
+                    .zip(explanations)
+                    .map(|(part, explanation)| format!("Part {part}\n{explanation}"))
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+                Err(e) => format!("{e:#}"),
+            };
+            // Nothing is listening once the dashboard has been closed.
+            let _ = sender.send(Done::Explanation(index, text));
+        });
+    }
+
+    fn select(&mut self, step: isize) {
+        let last = self.days.len().saturating_sub(1);
+        let selected = self.selected.selected().unwrap_or(0);
+
+        self.selected
+            .select(Some(selected.saturating_add_signed(step).min(last)));
+    }
+
+    /// Acts on a key, returning false once the dashboard should close.
+    fn key(&mut self, code: KeyCode) -> bool {
+        if let Some((_, scroll)) = &mut self.explanation {
+            match code {
+                KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => *scroll = scroll.saturating_add(1),
+                KeyCode::Char('q') => return false,
+                _ => {
+                    self.explanation = None;
+                    self.explaining = None;
+                }
+            }
+            return true;
+        }
+
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.select(1),
+            KeyCode::Enter | KeyCode::Char('r') => self.start(Job::Run),
+            KeyCode::Char('R') => self.start(Job::Rerun),
+            KeyCode::Char('b') => self.start(Job::Benchmark),
+            KeyCode::Char('e') => self.explain(),
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            _ => {}
+        }
+
+        true
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, log_area, help_area] = *Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(5),
+                Constraint::Percentage(30),
+                Constraint::Length(1),
+            ])
+            .split(frame.size())
+        else {
+            return;
+        };
+
+        let header = Row::new([
+            "Year", "Day", "Title", "Stars", "Part 1", "Part 2", "Time", "Verify",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        // The title takes whatever the other columns, the gaps between the
+        // eight columns and the borders leave.
+        let title = table_area.width.saturating_sub(84 + 7 + 2).max(16);
+        let table = Table::new(
+            self.days.iter().map(Day::row),
+            [
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Length(title),
+                Constraint::Length(5),
+                Constraint::Length(16),
+                Constraint::Length(16),
+                Constraint::Length(24),
+                Constraint::Length(16),
+            ],
+        )
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title("Days"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, table_area, &mut self.selected);
+
+        let lines = self.logs.last(log_area.height.saturating_sub(2) as usize);
+        let log = Paragraph::new(lines.join("\n"))
+            .block(Block::default().borders(Borders::ALL).title("Log"));
+        frame.render_widget(log, log_area);
+        frame.render_widget(Paragraph::new(HELP), help_area);
+
+        if let Some((text, scroll)) = &self.explanation {
+            let title = self
+                .selected
+                .selected()
+                .map_or(String::new(), |index| self.days[index].puzzle.name());
+            let area = Rect {
+                x: table_area.x + 2,
+                y: table_area.y + 1,
+                width: table_area.width.saturating_sub(4),
+                height: table_area.height.saturating_sub(2),
+            };
+            let explanation = Paragraph::new(text.as_str())
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .wrap(Wrap { trim: false })
+                .scroll((*scroll, 0));
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(explanation, area);
+        }
+    }
+
+    fn run(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        loop {
+            self.collect();
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(TICK)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.key(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+// Gives the terminal back however the dashboard ends, panics included.
+struct Restore;
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    }
+}
+
+pub fn run(
+    store: &Store,
+    cache: Option<Cache>,
+    history: Option<History>,
+    timeout: Option<Duration>,
+    repetitions: usize,
+    verbosity: u8,
+) -> Result<()> {
+    let logs = Logs::default();
+    logging::init_with_writer(verbosity.max(1), logs.clone());
+    progress::suppress();
+
+    let puzzles = aoc_registry::years().into_values().flatten().collect();
+    let mut app = App::new(puzzles, store, cache, history, timeout, repetitions, logs)?;
+
+    terminal::enable_raw_mode()?;
+    let _restore = Restore;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    app.run(&mut terminal)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path, time::Instant};
+
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+    use crate::inputs;
+
+    fn app(dir: &Path, cache: Option<Cache>) -> App {
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let store = Store {
+            plain: dir.to_path_buf(),
+            sealed: dir.join("sealed"),
+            key: None,
+            answers_file: None,
+        };
+        let input_path = dir.join(inputs::input_name(puzzle));
+        fs::create_dir_all(input_path.parent().unwrap()).unwrap();
+        fs::write(&input_path, puzzle.examples[0]).unwrap();
+        fs::write(dir.join(inputs::answers_name(puzzle)), "288\n1\n").unwrap();
+
+        let puzzles = vec![aoc_registry::find(2023, 5).unwrap(), puzzle];
+        App::new(puzzles, &store, cache, None, None, 2, Logs::default()).unwrap()
+    }
+
+    #[test]
+    fn test_days_start_from_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path().join("cache"));
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let solved = Solved {
+            answer: Answer::from(288_u32),
+            seconds: 0.5,
+        };
+        cache
+            .put(&Cache::key(puzzle, 1, puzzle.examples[0]), &solved)
+            .unwrap();
+
+        let app = app(dir.path(), Some(cache));
+        assert!(app.days[0].input.is_none());
+        assert_eq!(app.days[1].stars(), 1);
+        assert!(app.days[1].parts[1].is_none());
+    }
+
+    #[test]
+    fn test_keys_run_the_selected_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app(dir.path(), None);
+        let history = History::open(&dir.path().join("history.sqlite"), "abc".into()).unwrap();
+        app.history = Some(history);
+
+        assert!(app.key(KeyCode::Down));
+        assert!(app.key(KeyCode::Char('b')));
+        assert_eq!(app.days[1].busy, Some(Job::Benchmark));
+
+        let start = Instant::now();
+        while app.days[1].busy.is_some() {
+            assert!(start.elapsed() < Duration::from_secs(10), "still running");
+            thread::sleep(Duration::from_millis(10));
+            app.collect();
+        }
+
+        let day = &app.days[1];
+        assert_eq!(day.busy, None);
+        assert_eq!(
+            day.verdicts(),
+            [
+                Some(Verdict::Correct),
+                Some(Verdict::Wrong {
+                    expected: Answer::from(1_u32)
+                })
+            ]
+        );
+        assert!(matches!(&day.parts[0], Some(Outcome::Solved { note, .. }) if note == "best of 2"));
+
+        let records = app.history.as_ref().unwrap().records(day.puzzle).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, "benchmark");
+
+        assert!(app.key(KeyCode::Char('e')));
+        assert!(app.explanation.is_some());
+        let start = Instant::now();
+        while app.explaining.is_some() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "still explaining"
+            );
+            thread::sleep(Duration::from_millis(10));
+            app.collect();
+        }
+        // Day 6 cannot explain itself, which the worker reports in its place.
+        let (text, _) = app.explanation.as_ref().unwrap();
+        assert!(text.contains("cannot explain its answers"));
+        assert!(app.key(KeyCode::Esc));
+        assert!(app.explanation.is_none());
+        assert!(!app.key(KeyCode::Esc));
+    }
+
+    #[test]
+    fn test_draws_days_and_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app(dir.path(), None);
+        app.logs.push("INFO solved it");
+
+        let mut terminal = Terminal::new(TestBackend::new(140, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let screen: String = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect();
+
+        assert!(screen.contains("Wait For It"));
+        assert!(screen.contains("no input"));
+        assert!(screen.contains("INFO solved it"));
+        assert!(screen.contains(HELP));
+    }
+}
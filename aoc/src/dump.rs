@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde_json::Value;
+
+use aoc_common::{input, puzzle::Puzzle};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum DumpFormat {
+    Json,
+}
+
+pub enum Source<'a> {
+    Input(&'a str),
+    Example(usize),
+}
+
+pub fn parsed(puzzle: &Puzzle, source: Source) -> Result<Value> {
+    let contents = match source {
+        Source::Input(input) => input,
+        Source::Example(n) => n
+            .checked_sub(1)
+            .and_then(|i| puzzle.examples.get(i))
+            .with_context(|| format!("There are {} examples, not {n}", puzzle.examples.len()))?,
+    };
+
+    puzzle
+        .solution
+        .dump(&input::lines(contents, puzzle.input))
+        .with_context(|| format!("Could not parse the input of {}", puzzle.name()))
+}
+
+pub fn run(puzzle: &Puzzle, source: Source, format: DumpFormat) -> Result<()> {
+    let parsed = parsed(puzzle, source)?;
+
+    match format {
+        DumpFormat::Json => println!("{}", serde_json::to_string_pretty(&parsed)?),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_examples_dump() {
+        for puzzle in &aoc_registry::years()[&2023] {
+            let parsed = parsed(puzzle, Source::Example(1));
+            assert_eq!(parsed.is_ok(), puzzle.day != 1, "day {}", puzzle.day);
+        }
+    }
+
+    #[test]
+    fn test_dumped_races() {
+        let parsed = parsed(aoc_registry::find(2023, 6).unwrap(), Source::Example(1)).unwrap();
+
+        assert_eq!(parsed["races"][0], json!({"time": 7, "distance": 9}));
+        assert_eq!(parsed["big_race"]["time"], json!(71530));
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::{Context, Result};
+
+use aoc_common::{input, puzzle::Puzzle};
+
+/// Both parts' narratives of how the answer was reached, for the `item`th
+/// item of the input or for all of them.
+pub fn explanations(puzzle: &Puzzle, input: &str, item: Option<usize>) -> Result<Vec<String>> {
+    let lines = input::lines(input, puzzle.input);
+
+    (1..=2)
+        .map(|part| {
+            puzzle
+                .solution
+                .explain(&lines, part, item)
+                .with_context(|| format!("Could not explain {} part {part}", puzzle.name()))
+        })
+        .collect()
+}
+
+pub fn run(puzzle: &Puzzle, input: &str, item: Option<usize>) -> Result<()> {
+    println!("{} {:>2}  {}", puzzle.year, puzzle.day, puzzle.title);
+
+    for (part, explanation) in (1..).zip(explanations(puzzle, input, item)?) {
+        println!("\nPart {part}\n{explanation}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_explained() {
+        for puzzle in &aoc_registry::years()[&2023] {
+            let explained = explanations(puzzle, puzzle.examples[0], None);
+            assert_eq!(
+                explained.is_ok(),
+                [2, 7, 9].contains(&puzzle.day),
+                "day {}",
+                puzzle.day
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_item() {
+        let puzzle = aoc_registry::find(2023, 7).unwrap();
+        let error = explanations(puzzle, puzzle.examples[0], Some(6)).unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "There are 5 hands, not 6");
+    }
+}
@@ -0,0 +1,468 @@
+use std::{path::Path, process::Command, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, OpenFlags};
+use sha2::{Digest, Sha256};
+
+use aoc_common::puzzle::Puzzle;
+
+use crate::{cache::Solved, table::Table};
+
+pub const HISTORY_FILE: &str = ".aoc-history.sqlite";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        git_commit TEXT NOT NULL,
+        recorded_at TEXT NOT NULL DEFAULT (datetime('now')),
+        year INTEGER NOT NULL,
+        day INTEGER NOT NULL,
+        part INTEGER NOT NULL,
+        input TEXT NOT NULL,
+        answer TEXT NOT NULL,
+        seconds REAL NOT NULL,
+        kind TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS runs_by_day ON runs (year, day);
+";
+
+/// How a recorded time was taken: a single run, the fastest of several, or
+/// the fastest of several on an input generated by `aoc scale`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Kind {
+    Run,
+    Benchmark,
+    Scale,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Run => "run",
+            Kind::Benchmark => "benchmark",
+            Kind::Scale => "scale",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Record {
+    pub commit: String,
+    /// UTC, as `YYYY-MM-DD HH:MM:SS`.
+    pub recorded_at: String,
+    pub part: u8,
+    /// Hash of the input, as answers only compare on the same one.
+    pub input: String,
+    pub answer: String,
+    pub seconds: f64,
+    pub kind: String,
+}
+
+/// A short hash telling inputs apart.
+pub fn input_hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input))[..12].to_string()
+}
+
+/// Answers and timings of every recorded run, kept across commits in a SQLite
+/// database.
+pub struct History {
+    connection: Connection,
+    /// The commit new records are made at.
+    commit: String,
+}
+
+impl History {
+    pub fn open(path: &Path, commit: String) -> Result<History> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("Could not open the history in {}", path.display()))?;
+        connection
+            .execute_batch(SCHEMA)
+            .with_context(|| format!("{} is not a run history", path.display()))?;
+
+        Ok(History { connection, commit })
+    }
+
+    /// The history as recorded so far, only to be read. Nothing is created
+    /// when there is no history yet.
+    pub fn open_read_only(path: &Path) -> Result<History> {
+        if !path.exists() {
+            bail!(
+                "Nothing has been recorded in {} yet, record runs with --record",
+                path.display()
+            );
+        }
+
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Could not open the history in {}", path.display()))?;
+        connection
+            .prepare("SELECT 1 FROM runs")
+            .with_context(|| format!("{} is not a run history", path.display()))?;
+
+        Ok(History {
+            connection,
+            commit: String::new(),
+        })
+    }
+
+    pub fn record(
+        &self,
+        puzzle: &Puzzle,
+        part: u8,
+        input: &str,
+        solved: &Solved,
+        kind: Kind,
+    ) -> Result<()> {
+        self.connection
+            .execute(
+                "INSERT INTO runs (git_commit, year, day, part, input, answer, seconds, kind)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    self.commit,
+                    puzzle.year,
+                    puzzle.day,
+                    part,
+                    input_hash(input),
+                    solved.answer.to_string(),
+                    solved.seconds,
+                    kind.name(),
+                ],
+            )
+            .context("Could not record the run")?;
+
+        Ok(())
+    }
+
+    /// A puzzle's records, oldest first.
+    pub fn records(&self, puzzle: &Puzzle) -> Result<Vec<Record>> {
+        let mut statement = self.connection.prepare(
+            "SELECT git_commit, recorded_at, part, input, answer, seconds, kind FROM runs
+             WHERE year = ?1 AND day = ?2 ORDER BY id",
+        )?;
+        let records = statement
+            .query_map(params![puzzle.year, puzzle.day], |row| {
+                Ok(Record {
+                    commit: row.get(0)?,
+                    recorded_at: row.get(1)?,
+                    part: row.get(2)?,
+                    input: row.get(3)?,
+                    answer: row.get(4)?,
+                    seconds: row.get(5)?,
+                    kind: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(records)
+    }
+}
+
+/// The commit checked out in `dir`, with `-dirty` when tracked files have
+/// changed since, or `unknown` outside a git checkout.
+pub fn current_commit(dir: &Path) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(changes) if !changes.is_empty() => format!("{commit}-dirty"),
+            _ => commit,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// A stretch of records of one part made at the same commit on the same input,
+/// timed the same way.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Trend {
+    pub commit: String,
+    pub input: String,
+    pub kind: String,
+    /// When the last of them was made.
+    pub recorded_at: String,
+    pub runs: usize,
+    /// The last answer given.
+    pub answer: String,
+    pub best: f64,
+    pub mean: f64,
+}
+
+/// The records of `part` grouped into consecutive runs of one kind at one
+/// commit on one input, so that going back to an earlier commit starts a new
+/// group.
+pub fn trends(records: &[Record], part: u8) -> Vec<Trend> {
+    let mut trends: Vec<Trend> = vec![];
+
+    for record in records.iter().filter(|record| record.part == part) {
+        match trends.last_mut() {
+            Some(trend)
+                if (&trend.commit, &trend.input, &trend.kind)
+                    == (&record.commit, &record.input, &record.kind) =>
+            {
+                trend.mean =
+                    (trend.mean * trend.runs as f64 + record.seconds) / (trend.runs + 1) as f64;
+                trend.runs += 1;
+                trend.best = trend.best.min(record.seconds);
+                trend.answer = record.answer.clone();
+                trend.recorded_at = record.recorded_at.clone();
+            }
+            _ => trends.push(Trend {
+                commit: record.commit.clone(),
+                input: record.input.clone(),
+                kind: record.kind.clone(),
+                recorded_at: record.recorded_at.clone(),
+                runs: 1,
+                answer: record.answer.clone(),
+                best: record.seconds,
+                mean: record.seconds,
+            }),
+        }
+    }
+
+    trends
+}
+
+/// The trends as a table, with each change from the last commit timed the
+/// same way on the same input: a `*` beside an answer that changed, and how
+/// much faster or slower it got, unless the earlier time was too short to
+/// compare with.
+pub fn table(trends: &[Trend]) -> Table {
+    let time = |seconds: f64| format!("{:.2?}", Duration::from_secs_f64(seconds));
+
+    let rows = trends
+        .iter()
+        .enumerate()
+        .map(|(index, trend)| {
+            let previous = trends[..index]
+                .iter()
+                .rfind(|previous| (&previous.input, &previous.kind) == (&trend.input, &trend.kind));
+            let changed = previous.is_some_and(|previous| previous.answer != trend.answer);
+
+            let change = match previous {
+                None => String::new(),
+                Some(previous) => {
+                    let speed = match previous.best > 0.0 {
+                        true => format!("{:+.0}%", (trend.best / previous.best - 1.0) * 100.0),
+                        false => "–".to_string(),
+                    };
+                    match changed {
+                        true => format!("answer was {}, {speed}", previous.answer),
+                        false => speed,
+                    }
+                }
+            };
+            let marker = if changed { "* " } else { "" };
+
+            vec![
+                trend.commit.clone(),
+                trend.input[..8].to_string(),
+                trend.kind.clone(),
+                trend.recorded_at.clone(),
+                trend.runs.to_string(),
+                format!("{marker}{}", trend.answer),
+                time(trend.best),
+                time(trend.mean),
+                change,
+            ]
+        })
+        .collect();
+
+    Table {
+        headers: vec![
+            "Commit", "Input", "Kind", "Recorded", "Runs", "Answer", "Best", "Mean", "Change",
+        ],
+        rows,
+    }
+}
+
+pub fn run(history: &History, puzzle: &Puzzle) -> Result<()> {
+    let records = history.records(puzzle)?;
+    if records.is_empty() {
+        println!(
+            "Nothing has been recorded for {} of {}, run it with --record",
+            puzzle.name(),
+            puzzle.year
+        );
+        return Ok(());
+    }
+
+    println!("{} {:>2}  {}", puzzle.year, puzzle.day, puzzle.title);
+    for part in 1..=2 {
+        let trends = trends(&records, part);
+
+        println!("\nPart {part}");
+        match trends.is_empty() {
+            true => println!("Nothing recorded"),
+            false => print!("{}", table(&trends).to_text()),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::answer::Answer;
+
+    use super::*;
+
+    fn record(commit: &str, part: u8, answer: &str, seconds: f64) -> Record {
+        Record {
+            commit: commit.to_string(),
+            recorded_at: format!("2023-12-0{part} 00:00:00"),
+            part,
+            input: input_hash("input"),
+            answer: answer.to_string(),
+            seconds,
+            kind: "run".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let solved = Solved {
+            answer: Answer::from(288_u32),
+            seconds: 0.25,
+        };
+
+        let history = History::open(&path, "abc1234".to_string()).unwrap();
+        history
+            .record(puzzle, 1, "Time: 7", &solved, Kind::Run)
+            .unwrap();
+        drop(history);
+
+        let history = History::open(&path, "def5678".to_string()).unwrap();
+        history
+            .record(puzzle, 1, "Time: 8", &solved, Kind::Benchmark)
+            .unwrap();
+
+        let records = history.records(puzzle).unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.commit.as_str(), record.kind.as_str()))
+                .collect::<Vec<_>>(),
+            [("abc1234", "run"), ("def5678", "benchmark")]
+        );
+        assert_eq!(records[0].input, input_hash("Time: 7"));
+        assert_ne!(records[1].input, records[0].input);
+        assert_eq!(records[0].answer, "288");
+        assert_eq!(records[0].seconds, 0.25);
+        assert!(history
+            .records(aoc_registry::find(2023, 7).unwrap())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_reading_creates_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+
+        let error = History::open_read_only(&path).err().unwrap();
+        assert!(error.to_string().contains("--record"));
+        assert!(!path.exists());
+
+        History::open(&path, "abc1234".to_string()).unwrap();
+        let history = History::open_read_only(&path).unwrap();
+        assert!(history.records(puzzle).unwrap().is_empty());
+        let solved = Solved {
+            answer: Answer::from(288_u32),
+            seconds: 0.25,
+        };
+        assert!(history
+            .record(puzzle, 1, "Time: 7", &solved, Kind::Run)
+            .is_err());
+    }
+
+    #[test]
+    fn test_trends_group_consecutive_commits() {
+        let records = [
+            record("a", 1, "142", 0.004),
+            record("a", 2, "281", 0.010),
+            record("a", 1, "142", 0.002),
+            record("b", 1, "142", 0.001),
+            record("a", 1, "142", 0.003),
+        ];
+
+        let trends = trends(&records, 1);
+        assert_eq!(
+            trends
+                .iter()
+                .map(|trend| (trend.commit.as_str(), trend.runs))
+                .collect::<Vec<_>>(),
+            [("a", 2), ("b", 1), ("a", 1)]
+        );
+        assert_eq!((trends[0].best, trends[0].mean), (0.002, 0.003));
+    }
+
+    #[test]
+    fn test_table_highlights_answer_changes() {
+        let other_input = Record {
+            input: input_hash("other"),
+            ..record("c", 1, "7", 0.001)
+        };
+        let records = [
+            record("a", 1, "142", 0.002),
+            record("b", 1, "142", 0.001),
+            other_input,
+            record("c", 1, "143", 0.001),
+        ];
+
+        let table = table(&trends(&records, 1));
+        assert_eq!(table.rows[0][8], "");
+        assert_eq!(table.rows[1][5], "142");
+        assert_eq!(table.rows[1][8], "-50%");
+        assert_eq!(table.rows[2][8], "");
+        assert_eq!(table.rows[3][5], "* 143");
+        assert_eq!(table.rows[3][8], "answer was 142, +0%");
+    }
+
+    #[test]
+    fn test_kinds_are_kept_apart() {
+        let scaled = Record {
+            kind: "scale".to_string(),
+            ..record("a", 1, "9", 0.5)
+        };
+        let records = [
+            record("a", 1, "142", 0.002),
+            scaled.clone(),
+            record("a", 1, "142", 0.004),
+            Record {
+                commit: "b".to_string(),
+                ..scaled
+            },
+        ];
+
+        let trends = trends(&records, 1);
+        assert_eq!(
+            trends
+                .iter()
+                .map(|trend| (trend.kind.as_str(), trend.runs))
+                .collect::<Vec<_>>(),
+            [("run", 1), ("scale", 1), ("run", 1), ("scale", 1)]
+        );
+        let table = table(&trends);
+        assert_eq!(table.rows[2][8], "+100%");
+        assert_eq!(table.rows[3][8], "+0%");
+    }
+
+    #[test]
+    fn test_change_from_no_time_is_not_a_ratio() {
+        let records = [record("a", 1, "142", 0.0), record("b", 1, "142", 0.001)];
+
+        let table = table(&trends(&records, 1));
+        assert_eq!(table.rows[1][8], "–");
+    }
+}
@@ -0,0 +1,221 @@
+use std::{cmp::Reverse, collections::HashMap, fs, path::Path, ptr};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use aoc_common::{
+    input::{self, Normalization},
+    puzzle::Puzzle,
+};
+
+use crate::table::Table;
+
+pub struct Candidate {
+    pub puzzle: &'static Puzzle,
+    /// Words and numbers of the file that turn up in the parsed structure.
+    pub covered: usize,
+    pub total: usize,
+    /// How much of the other days' examples the parser also makes sense of,
+    /// in thousandths. Loose grammars, like a plain grid of characters, accept
+    /// nearly anything and so say little about where a file came from.
+    pub looseness: usize,
+}
+
+impl Candidate {
+    pub fn coverage(&self) -> f64 {
+        self.covered as f64 / self.total.max(1) as f64
+    }
+}
+
+fn parse(puzzle: &Puzzle, lines: &[&str]) -> Option<Value> {
+    puzzle.solution.dump(lines).ok()
+}
+
+fn tokens(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+}
+
+// A single digit, bare or wrapped in an enum variant like `{"Number": 4}`.
+fn digit(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64().filter(|&n| n < 10),
+        Value::Object(fields) if fields.len() == 1 => digit(fields.values().next()?),
+        _ => None,
+    }
+}
+
+fn leaves(value: &Value, found: &mut HashMap<String, usize>) {
+    match value {
+        Value::Array(values) => {
+            // Grids keep numbers a digit per cell, so runs of digits are read
+            // back as the numbers they spell.
+            let mut run = String::new();
+            for value in values.iter().map(Some).chain([None]) {
+                match value.and_then(digit) {
+                    Some(d) => run.push(char::from_digit(d as u32, 10).unwrap()),
+                    None if run.len() > 1 => *found.entry(run.split_off(0)).or_default() += 1,
+                    None => run.clear(),
+                }
+            }
+
+            values.iter().for_each(|value| leaves(value, found));
+        }
+        Value::Object(fields) => fields.values().for_each(|value| leaves(value, found)),
+        Value::String(s) => tokens(s).for_each(|token| *found.entry(token).or_default() += 1),
+        Value::Number(n) => *found.entry(n.to_string()).or_default() += 1,
+        Value::Bool(_) | Value::Null => {}
+    }
+}
+
+/// Counts the tokens of `contents` that each appear in the parsed value,
+/// every value in it matching at most one token.
+fn coverage(parsed: &Value, contents: &str) -> (usize, usize) {
+    let mut found = HashMap::new();
+    leaves(parsed, &mut found);
+
+    let (mut covered, mut total) = (0, 0);
+    for token in tokens(contents) {
+        total += 1;
+        if let Some(count) = found.get_mut(&token).filter(|count| **count > 0) {
+            *count -= 1;
+            covered += 1;
+        }
+    }
+
+    (covered, total)
+}
+
+fn thousandths(covered: usize, total: usize) -> usize {
+    covered * 1000 / total.max(1)
+}
+
+fn coverage_of(puzzle: &Puzzle, contents: &str, normalization: Normalization) -> (usize, usize) {
+    parse(puzzle, &input::normalize(contents, normalization).lines)
+        .map_or((0, 0), |parsed| coverage(&parsed, contents))
+}
+
+/// Every day whose parser accepts `contents`, best match first: most of the
+/// file reflected in what was parsed, then the strictest grammar. Days without
+/// a dump, like 2023 day 1 which reads its lines as they are, are never found.
+pub fn candidates(puzzles: &[&'static Puzzle], contents: &str) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = puzzles
+        .iter()
+        .filter_map(|&puzzle| {
+            let (covered, total) = coverage_of(puzzle, contents, puzzle.input);
+
+            (covered > 0).then(|| Candidate {
+                puzzle,
+                covered,
+                total,
+                looseness: puzzles
+                    .iter()
+                    .filter(|other| !ptr::eq(**other, puzzle))
+                    .flat_map(|&other| other.examples.iter().map(move |e| (other, e)))
+                    .map(|(other, example)| {
+                        let (covered, total) = coverage_of(puzzle, example, other.input);
+                        thousandths(covered, total)
+                    })
+                    .sum(),
+            })
+        })
+        .collect();
+
+    candidates.sort_by_key(|candidate| {
+        (
+            Reverse(thousandths(candidate.covered, candidate.total)),
+            candidate.looseness,
+            candidate.puzzle.year,
+            candidate.puzzle.day,
+        )
+    });
+    candidates
+}
+
+pub fn run(path: &Path) -> Result<()> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))?;
+    let puzzles: Vec<&'static Puzzle> = aoc_registry::years().into_values().flatten().collect();
+
+    let candidates = candidates(&puzzles, &contents);
+    if candidates.is_empty() {
+        println!("No registered parser accepts {}", path.display());
+        return Ok(());
+    }
+
+    let table = Table {
+        headers: vec!["Year", "Day", "Title", "Coverage", "Tokens"],
+        rows: candidates
+            .iter()
+            .map(|candidate| {
+                vec![
+                    candidate.puzzle.year.to_string(),
+                    candidate.puzzle.day.to_string(),
+                    candidate.puzzle.title.to_string(),
+                    format!("{:.0}%", candidate.coverage() * 100.0),
+                    format!("{} of {}", candidate.covered, candidate.total),
+                ]
+            })
+            .collect(),
+    };
+    print!("{}", table.to_text());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzles() -> Vec<&'static Puzzle> {
+        aoc_registry::years().into_values().flatten().collect()
+    }
+
+    // Day 1 has no parsed structure to dump, so there is nothing to identify
+    // it by.
+    #[test]
+    fn test_examples_identify_their_day() {
+        let puzzles = puzzles();
+
+        for day in 2..=9 {
+            let puzzle = aoc_registry::find(2023, day).unwrap();
+            let best = &candidates(&puzzles, puzzle.examples[0])[0];
+
+            assert_eq!(best.puzzle.day, day);
+        }
+    }
+
+    #[test]
+    fn test_coverage_counts_each_value_once() {
+        let parsed = serde_json::json!({"seeds": [79, 14], "maps": [{"name": "seed-to-soil"}]});
+
+        assert_eq!(coverage(&parsed, "seeds: 79 14 79"), (2, 4));
+        assert_eq!(coverage(&parsed, "Seed to soil"), (3, 3));
+
+        let grid = serde_json::json!([[{"Number": 4}, {"Number": 6}, "x", {"Number": 7}]]);
+        assert_eq!(coverage(&grid, "46.7"), (2, 2));
+    }
+
+    #[test]
+    fn test_garbage_is_not_identified() {
+        assert!(candidates(&puzzles(), "").is_empty());
+        assert!(candidates(&puzzles(), "Time: 7\n")
+            .iter()
+            .all(|c| c.puzzle.day != 6));
+    }
+
+    #[test]
+    fn test_parsers_do_not_panic_on_partial_inputs() {
+        for puzzle in puzzles() {
+            for example in puzzle.examples {
+                let lines: Vec<&str> = example.lines().collect();
+
+                for end in 0..lines.len() {
+                    parse(puzzle, &lines[..end]);
+                    parse(puzzle, &lines[end..]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,480 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use tracing::{debug, info};
+
+use aoc_common::puzzle::Puzzle;
+
+pub const INPUTS_DIR: &str = "inputs";
+pub const SEALED_DIR: &str = "sealed";
+pub const KEY_FILE: &str = ".aoc-key";
+pub const KEY_VARIABLE: &str = "AOC_INPUTS_KEY";
+
+const MAGIC: &[u8] = b"AOC1";
+const NONCE_LEN: usize = 24;
+
+/// Where the input of a puzzle lives, relative to the inputs directory, e.g.
+/// `2023/day_05.txt`.
+pub fn input_name(puzzle: &Puzzle) -> PathBuf {
+    Path::new(&puzzle.year.to_string()).join(format!("day_{:02}.txt", puzzle.day))
+}
+
+/// Accepted answers are kept beside the input, one part per line.
+pub fn answers_name(puzzle: &Puzzle) -> PathBuf {
+    input_name(puzzle).with_extension("answers")
+}
+
+pub struct Key(chacha20poly1305::Key);
+
+impl Key {
+    /// The key from the environment, or else from the key file if there is one.
+    pub fn load(key_file: &Path) -> Result<Option<Key>> {
+        let hex = match env::var(KEY_VARIABLE) {
+            Ok(hex) => hex,
+            Err(_) => match fs::read_to_string(key_file) {
+                Ok(hex) => hex,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Could not read {}", key_file.display()))
+                }
+            },
+        };
+
+        Key::from_hex(hex.trim()).map(Some)
+    }
+
+    pub fn generate() -> Key {
+        Key(XChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Key> {
+        let invalid = || anyhow!("The inputs key must be 64 hexadecimal digits");
+
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid()))
+            .collect::<Result<Vec<u8>>>()?;
+
+        Ok(Key(*chacha20poly1305::Key::from_slice(&bytes)))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Writes the key to a new file that only its owner can read. An existing
+    /// file is never replaced, since inputs sealed with it would be lost.
+    pub fn save(&self, key_file: &Path) -> Result<()> {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = match options.open(key_file) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                bail!("{} already exists, will not replace it", key_file.display())
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Could not create {}", key_file.display()))
+            }
+        };
+
+        writeln!(file, "{}", self.to_hex())
+            .with_context(|| format!("Could not write {}", key_file.display()))
+    }
+
+    /// Encrypts `plaintext`, binding it to `name` so that sealed files cannot
+    /// be swapped around.
+    pub fn seal(&self, name: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: plaintext,
+            aad: name.as_bytes(),
+        };
+        let ciphertext = XChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, payload)
+            .map_err(|_| anyhow!("Could not encrypt {name}"))?;
+
+        Ok([MAGIC, nonce.as_slice(), &ciphertext].concat())
+    }
+
+    pub fn unseal(&self, name: &str, sealed: &[u8]) -> Result<Vec<u8>> {
+        let Some(rest) = sealed
+            .strip_prefix(MAGIC)
+            .filter(|rest| rest.len() >= NONCE_LEN)
+        else {
+            bail!("{name} is not a sealed input");
+        };
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: name.as_bytes(),
+        };
+
+        XChaCha20Poly1305::new(&self.0)
+            .decrypt(XNonce::from_slice(nonce), payload)
+            .map_err(|_| anyhow!("{name} was sealed with another key or has been altered"))
+    }
+}
+
+/// Puzzle inputs and answers, read from the plain inputs directory or else
+/// decrypted from the sealed one when the key is available.
+pub struct Store {
+    pub plain: PathBuf,
+    pub sealed: PathBuf,
+    pub key: Option<Key>,
+    /// One file of answers for every puzzle, in place of `.answers` files.
+    pub answers_file: Option<PathBuf>,
+}
+
+impl Store {
+    pub fn open(
+        plain: PathBuf,
+        sealed: PathBuf,
+        key_file: &Path,
+        answers_file: Option<PathBuf>,
+    ) -> Result<Store> {
+        Ok(Store {
+            plain,
+            sealed,
+            key: Key::load(key_file)?,
+            answers_file,
+        })
+    }
+
+    pub fn read(&self, name: &Path) -> Result<Option<String>> {
+        let plain_path = self.plain.join(name);
+        match fs::read_to_string(&plain_path) {
+            Ok(contents) => return Ok(Some(contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Could not read {}", plain_path.display()))
+            }
+        }
+
+        let sealed_path = sealed_path(&self.sealed, name);
+        let sealed = match fs::read(&sealed_path) {
+            Ok(sealed) => sealed,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Could not read {}", sealed_path.display()))
+            }
+        };
+
+        let Some(key) = &self.key else {
+            debug!(path = %sealed_path.display(), "no key to unseal input");
+            return Ok(None);
+        };
+
+        let plaintext = key.unseal(&sealed_name(name), &sealed)?;
+        String::from_utf8(plaintext)
+            .map(Some)
+            .with_context(|| format!("{} is not text", sealed_path.display()))
+    }
+
+    pub fn input(&self, puzzle: &Puzzle) -> Result<Option<String>> {
+        self.read(&input_name(puzzle))
+    }
+
+    /// The accepted answers of a puzzle, one part per line.
+    pub fn answers(&self, puzzle: &Puzzle) -> Result<Option<String>> {
+        match &self.answers_file {
+            Some(path) => {
+                let contents = match fs::read_to_string(path) {
+                    Ok(contents) => contents,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+                    Err(e) => {
+                        return Err(e).with_context(|| format!("Could not read {}", path.display()))
+                    }
+                };
+
+                Ok(answers_line(&contents, puzzle))
+            }
+            None => self.read(&answers_name(puzzle)),
+        }
+    }
+}
+
+/// Finds the puzzle in an answers file of `<year> <day> <part one> <part two>`
+/// lines, where `-` stands for a part without an accepted answer and `#`
+/// starts a comment.
+fn answers_line(contents: &str, puzzle: &Puzzle) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let line = line.split('#').next().unwrap_or_default();
+        let mut fields = line.split_whitespace();
+
+        let year = fields.next()?.parse::<u32>().ok()?;
+        let day = fields.next()?.parse::<u32>().ok()?;
+        if (year, day) != (puzzle.year, puzzle.day) {
+            return None;
+        }
+
+        let answers: Vec<&str> = fields
+            .map(|answer| if answer == "-" { "" } else { answer })
+            .collect();
+        Some(answers.join("\n"))
+    })
+}
+
+/// The contents of `file` when one is given, or else the stored input of the
+/// puzzle.
+pub fn read_input(puzzle: &Puzzle, file: Option<&Path>, store: &Store) -> Result<String> {
+    if let Some(file) = file {
+        return fs::read_to_string(file)
+            .with_context(|| format!("Could not read {}", file.display()));
+    }
+
+    store.input(puzzle)?.ok_or_else(|| {
+        anyhow!(
+            "No input for {} in {}, nor a sealed one that can be unsealed",
+            puzzle.name(),
+            store.plain.display()
+        )
+    })
+}
+
+fn sealed_path(sealed_dir: &Path, name: &Path) -> PathBuf {
+    let mut path = sealed_dir.join(name).into_os_string();
+    path.push(".sealed");
+    PathBuf::from(path)
+}
+
+// Names are bound into the ciphertext, so they are written the same way on
+// every platform.
+fn sealed_name(name: &Path) -> String {
+    name.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn files(dir: &Path, relative: &Path, found: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = match fs::read_dir(dir.join(relative)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).with_context(|| format!("Could not read {}", dir.display())),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let name = relative.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            files(dir, &name, found)?;
+        } else {
+            found.push(name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Encrypts every file of the inputs directory into the sealed directory,
+/// creating the key file first if there is no key. Returns how many files
+/// were sealed and how many were already up to date.
+pub fn seal(store: &Store, key_file: &Path) -> Result<(usize, usize)> {
+    let generated;
+    let key = match &store.key {
+        Some(key) => key,
+        None => {
+            generated = Key::generate();
+            generated.save(key_file)?;
+            info!(path = %key_file.display(), "generated a new inputs key");
+            &generated
+        }
+    };
+
+    let mut names = vec![];
+    files(&store.plain, Path::new(""), &mut names)?;
+
+    let (mut sealed, mut unchanged) = (0, 0);
+    for name in names {
+        let plaintext = fs::read(store.plain.join(&name))?;
+        let path = sealed_path(&store.sealed, &name);
+
+        let current = fs::read(&path)
+            .ok()
+            .and_then(|existing| key.unseal(&sealed_name(&name), &existing).ok());
+        if current.as_deref() == Some(&plaintext[..]) {
+            unchanged += 1;
+            continue;
+        }
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, key.seal(&sealed_name(&name), &plaintext)?)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        sealed += 1;
+    }
+
+    Ok((sealed, unchanged))
+}
+
+/// Decrypts every sealed file into the inputs directory. Plain files that
+/// differ from their sealed version are only replaced when forced. Returns
+/// how many files were written and how many were kept.
+pub fn unseal(store: &Store, force: bool) -> Result<(usize, usize)> {
+    let key = store.key.as_ref().ok_or(anyhow!(
+        "No key to unseal with, put it in {KEY_FILE} or {KEY_VARIABLE}"
+    ))?;
+
+    let mut names = vec![];
+    files(&store.sealed, Path::new(""), &mut names)?;
+
+    let (mut written, mut kept) = (0, 0);
+    for sealed_name_path in names {
+        let Some(name) = sealed_name_path
+            .to_str()
+            .and_then(|name| name.strip_suffix(".sealed"))
+            .map(PathBuf::from)
+        else {
+            continue;
+        };
+
+        let sealed = fs::read(store.sealed.join(&sealed_name_path))?;
+        let plaintext = key.unseal(&sealed_name(&name), &sealed)?;
+        let path = store.plain.join(&name);
+
+        match fs::read(&path) {
+            Ok(existing) if existing == plaintext => {
+                kept += 1;
+                continue;
+            }
+            Ok(_) if !force => {
+                eprintln!(
+                    "{} differs from its sealed version, kept it",
+                    path.display()
+                );
+                kept += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, plaintext)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        written += 1;
+    }
+
+    Ok((written, kept))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(dir: &Path, key: Option<Key>) -> Store {
+        Store {
+            plain: dir.join("inputs"),
+            sealed: dir.join("sealed"),
+            key,
+            answers_file: None,
+        }
+    }
+
+    #[test]
+    fn test_seal_and_unseal() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("key");
+        let name = Path::new("2023").join("day_06.txt");
+
+        fs::create_dir_all(dir.path().join("inputs/2023")).unwrap();
+        fs::write(dir.path().join("inputs").join(&name), "Time: 7\n").unwrap();
+
+        assert_eq!(seal(&store(dir.path(), None), &key_file).unwrap(), (1, 0));
+        let key = || Key::from_hex(fs::read_to_string(&key_file).unwrap().trim()).unwrap();
+        assert_eq!(
+            seal(&store(dir.path(), Some(key())), &key_file).unwrap(),
+            (0, 1)
+        );
+        assert!(seal(&store(dir.path(), None), &key_file).is_err());
+
+        fs::remove_dir_all(dir.path().join("inputs")).unwrap();
+        let sealed_only = store(dir.path(), Some(key()));
+        assert_eq!(
+            sealed_only.read(&name).unwrap().as_deref(),
+            Some("Time: 7\n")
+        );
+
+        assert_eq!(unseal(&sealed_only, false).unwrap(), (1, 0));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("inputs").join(&name)).unwrap(),
+            "Time: 7\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("key");
+
+        Key::generate().save(&key_file).unwrap();
+        let mode = fs::metadata(&key_file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(Key::generate().save(&key_file).is_err());
+    }
+
+    #[test]
+    fn test_missing_key_reads_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let name = Path::new("2023/day_06.txt");
+        let sealed = Key::generate()
+            .seal("2023/day_06.txt", b"Time: 7\n")
+            .unwrap();
+
+        fs::create_dir_all(dir.path().join("sealed/2023")).unwrap();
+        fs::write(sealed_path(&dir.path().join("sealed"), name), sealed).unwrap();
+
+        assert_eq!(store(dir.path(), None).read(name).unwrap(), None);
+        assert!(store(dir.path(), Some(Key::generate())).read(name).is_err());
+    }
+
+    #[test]
+    fn test_names() {
+        let puzzle = aoc_registry::find(2023, 5).unwrap();
+
+        assert_eq!(input_name(puzzle), Path::new("2023/day_05.txt"));
+        assert_eq!(answers_name(puzzle), Path::new("2023/day_05.answers"));
+        assert_eq!(sealed_name(&input_name(puzzle)), "2023/day_05.txt");
+    }
+
+    #[test]
+    fn test_answers_file() {
+        let contents = "# year day part_one part_two\n2023 5 35 46\n2023 6 - 71503 # late\n";
+
+        let day = |day| answers_line(contents, aoc_registry::find(2023, day).unwrap());
+        assert_eq!(day(5).as_deref(), Some("35\n46"));
+        assert_eq!(day(6).as_deref(), Some("\n71503"));
+        assert_eq!(day(7), None);
+    }
+
+    #[test]
+    fn test_sealed_files_are_bound_to_their_name() {
+        let key = Key::generate();
+        let sealed = key.seal("2023/day_05.txt", b"seeds: 1 2").unwrap();
+
+        assert_eq!(
+            key.unseal("2023/day_05.txt", &sealed).unwrap(),
+            b"seeds: 1 2"
+        );
+        assert!(key.unseal("2023/day_06.txt", &sealed).is_err());
+        assert!(Key::from_hex(&key.to_hex()).is_ok());
+        assert!(Key::from_hex("abc").is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use std::{cmp::Reverse, collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::table::Table;
+
+// Puzzles unlock at midnight EST, which is 05:00 UTC.
+const UNLOCK_HOUR_UTC: i64 = 5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum Report {
+    /// Stars and local score of each member
+    Summary,
+    /// Time each member took to get from part one to part two
+    Deltas,
+    /// Each member's rank after every day
+    Ranks,
+}
+
+/// A private leaderboard as exported from its JSON API link.
+#[derive(Deserialize, Debug)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<u64, Member>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u8, Star>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Star {
+    #[serde(deserialize_with = "timestamp")]
+    pub get_star_ts: i64,
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+// Older exports store timestamps as strings.
+fn timestamp<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        Number(i64),
+        Text(String),
+    }
+
+    match Timestamp::deserialize(deserializer)? {
+        Timestamp::Number(n) => Ok(n),
+        Timestamp::Text(s) => s.parse().map_err(de::Error::custom),
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn star(&self, day: u32, part: u8) -> Option<&Star> {
+        self.completion_day_level.get(&day)?.get(&part)
+    }
+}
+
+impl Leaderboard {
+    pub fn load(path: &Path) -> Result<Leaderboard> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+
+        serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not a leaderboard export", path.display()))
+    }
+
+    fn last_day(&self) -> u32 {
+        self.members
+            .values()
+            .flat_map(|member| member.completion_day_level.keys())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Local score points won by each member on each day: for every star, the
+    /// first member to get it scores one point per member, the next one less
+    /// and so on.
+    pub fn day_points(&self) -> BTreeMap<u32, BTreeMap<u64, u64>> {
+        let member_count = self.members.len() as u64;
+        let mut points: BTreeMap<u32, BTreeMap<u64, u64>> = BTreeMap::new();
+
+        for day in 1..=self.last_day() {
+            let day_points = points.entry(day).or_default();
+
+            for part in 1..=2 {
+                let mut finishers: Vec<(i64, u64, u64)> = self
+                    .members
+                    .values()
+                    .filter_map(|member| {
+                        let star = member.star(day, part)?;
+                        Some((star.get_star_ts, star.star_index, member.id))
+                    })
+                    .collect();
+                finishers.sort();
+
+                for (position, (_, _, id)) in finishers.into_iter().enumerate() {
+                    *day_points.entry(id).or_default() += member_count - position as u64;
+                }
+            }
+        }
+
+        points
+    }
+
+    pub fn local_scores(&self) -> BTreeMap<u64, u64> {
+        let mut scores: BTreeMap<u64, u64> = self.members.keys().map(|&id| (id, 0)).collect();
+
+        for day_points in self.day_points().values() {
+            for (id, points) in day_points {
+                *scores.entry(*id).or_default() += points;
+            }
+        }
+
+        scores
+    }
+
+    pub fn summary(&self) -> Table {
+        let scores = self.local_scores();
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|member| (Reverse(scores[&member.id]), member.id));
+
+        Table {
+            headers: vec!["Member", "Stars", "Local score", "Recomputed"],
+            rows: members
+                .iter()
+                .map(|member| {
+                    vec![
+                        member.display_name(),
+                        member.stars.to_string(),
+                        member.local_score.to_string(),
+                        scores[&member.id].to_string(),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    pub fn deltas(&self) -> Result<Table> {
+        let year: i64 = self
+            .event
+            .parse()
+            .with_context(|| format!("{} is not an event year", self.event))?;
+
+        let mut rows = vec![];
+
+        for day in 1..=self.last_day() {
+            let unlock = days_from_civil(year, 12, day as i64) * 86_400 + UNLOCK_HOUR_UTC * 3_600;
+
+            let mut finishers: Vec<(Option<i64>, &Member, i64)> = self
+                .members
+                .values()
+                .filter_map(|member| {
+                    let part_one = member.star(day, 1)?.get_star_ts;
+                    let part_two = member.star(day, 2).map(|star| star.get_star_ts);
+                    Some((part_two.map(|ts| ts - part_one), member, part_one))
+                })
+                .collect();
+            finishers.sort_by_key(|(delta, member, _)| (delta.is_none(), *delta, member.id));
+
+            for (delta, member, part_one) in finishers {
+                let part_two = delta.map(|delta| part_one + delta);
+
+                rows.push(vec![
+                    member.display_name(),
+                    day.to_string(),
+                    clock(part_one - unlock),
+                    part_two.map_or(String::new(), |ts| clock(ts - unlock)),
+                    delta.map_or(String::new(), clock),
+                ]);
+            }
+        }
+
+        Ok(Table {
+            headers: vec!["Member", "Day", "Part 1", "Part 2", "Delta"],
+            rows,
+        })
+    }
+
+    /// Rank after each day by the local score so far. Tied members share a
+    /// rank, and the change is how many places a member moved up.
+    pub fn ranks(&self) -> Table {
+        let mut totals: BTreeMap<u64, u64> = self.members.keys().map(|&id| (id, 0)).collect();
+        let mut previous_ranks: BTreeMap<u64, usize> = BTreeMap::new();
+        let mut rows = vec![];
+
+        for (day, day_points) in self.day_points() {
+            for (id, points) in day_points {
+                *totals.entry(id).or_default() += points;
+            }
+
+            let mut standings: Vec<(u64, u64)> = totals.iter().map(|(&id, &s)| (id, s)).collect();
+            standings.sort_by_key(|&(id, score)| (Reverse(score), id));
+
+            for &(id, score) in &standings {
+                let rank = 1 + standings.iter().filter(|(_, other)| *other > score).count();
+                let change = match previous_ranks.get(&id) {
+                    None => String::new(),
+                    Some(&previous) if previous == rank => "=".to_string(),
+                    Some(&previous) => format!("{:+}", previous as i64 - rank as i64),
+                };
+
+                rows.push(vec![
+                    day.to_string(),
+                    self.members[&id].display_name(),
+                    score.to_string(),
+                    rank.to_string(),
+                    change,
+                ]);
+                previous_ranks.insert(id, rank);
+            }
+        }
+
+        Table {
+            headers: vec!["Day", "Member", "Score", "Rank", "Change"],
+            rows,
+        }
+    }
+}
+
+pub fn run(path: &Path, report: Option<Report>, csv: bool) -> Result<()> {
+    let leaderboard = Leaderboard::load(path)?;
+
+    let reports = match report {
+        Some(report) => vec![report],
+        None => vec![Report::Summary, Report::Deltas, Report::Ranks],
+    };
+
+    for (i, report) in reports.into_iter().enumerate() {
+        let (title, table) = match report {
+            Report::Summary => ("Summary", leaderboard.summary()),
+            Report::Deltas => ("Part two deltas", leaderboard.deltas()?),
+            Report::Ranks => ("Ranks by day", leaderboard.ranks()),
+        };
+
+        if csv {
+            print!("{}", table.to_csv());
+        } else {
+            if i > 0 {
+                println!();
+            }
+            println!("{title}\n");
+            print!("{}", table.to_text());
+        }
+    }
+
+    Ok(())
+}
+
+fn clock(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Leaderboard {
+        serde_json::from_str(include_str!("../fixtures/leaderboard.json")).unwrap()
+    }
+
+    #[test]
+    fn test_local_scores_match_the_export() {
+        let leaderboard = fixture();
+        let scores = leaderboard.local_scores();
+
+        assert_eq!(scores, BTreeMap::from([(1, 8), (2, 7), (3, 7)]));
+        for member in leaderboard.members.values() {
+            assert_eq!(scores[&member.id], member.local_score);
+        }
+
+        assert_eq!(
+            leaderboard.summary().rows[2],
+            vec!["(anonymous user #3)", "3", "7", "7"]
+        );
+    }
+
+    #[test]
+    fn test_deltas() {
+        let rows = fixture().deltas().unwrap().rows;
+
+        assert_eq!(rows[0], vec!["Alice", "1", "0:03:20", "0:08:20", "0:05:00"]);
+        assert_eq!(
+            rows[1],
+            vec!["Bob, Jr.", "1", "0:01:40", "0:20:00", "0:18:20"]
+        );
+        assert_eq!(rows[2], vec!["(anonymous user #3)", "1", "0:53:20", "", ""]);
+        assert_eq!(rows[3][..2], ["(anonymous user #3)", "2"]);
+        assert_eq!(rows.len(), 6);
+    }
+
+    #[test]
+    fn test_rank_changes() {
+        let rows = fixture().ranks().rows;
+        let day_two: Vec<(&str, &str)> = rows[3..]
+            .iter()
+            .map(|row| (row[3].as_str(), row[4].as_str()))
+            .collect();
+
+        assert_eq!(rows[0][3..], ["1", ""]);
+        assert_eq!(rows[2][3..], ["3", ""]);
+        assert_eq!(day_two, vec![("1", "="), ("2", "-1"), ("2", "+1")]);
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let csv = fixture().summary().to_csv();
+
+        assert_eq!(
+            csv,
+            "Member,Stars,Local score,Recomputed\n\
+             Alice,4,8,8\n\
+             \"Bob, Jr.\",3,7,7\n\
+             (anonymous user #3),3,7,7\n"
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Result};
+use aoc_common::{cli::Format, logging};
+use clap::{ArgAction, Parser, Subcommand};
+
+use crate::config::Config;
+
+pub mod anonymize;
+pub mod batch;
+pub mod cache;
+pub mod config;
+pub mod dashboard;
+pub mod dump;
+pub mod explain;
+pub mod history;
+pub mod identify;
+pub mod inputs;
+pub mod leaderboard;
+pub mod plugins;
+pub mod progress;
+pub mod readme;
+pub mod run;
+pub mod scale;
+pub mod serve;
+pub mod table;
+pub mod verify;
+
+#[derive(Parser)]
+#[command(about = "Runs and inspects the Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Log what is going on to stderr, repeat for more detail
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Give up on a part after this many seconds, 0 for no limit
+    #[arg(long, value_name = "SECONDS", global = true)]
+    timeout: Option<f64>,
+
+    /// Settings file, instead of the nearest `aoc.toml`
+    #[arg(long, value_name = "FILE", global = true)]
+    config: Option<PathBuf>,
+
+    /// Directory holding `<year>/day_<NN>.txt` inputs and `.answers` files
+    #[arg(long, value_name = "DIR", global = true)]
+    inputs: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List every registered puzzle by year
+    List,
+    /// Rewrite an input into a look-alike that is safe to share
+    Anonymize {
+        day: u32,
+        /// Defaults to the latest year with solutions
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Puzzle input file instead of the one in the inputs directory
+        #[arg(short, long, value_name = "FILE")]
+        input: Option<PathBuf>,
+        /// Write the anonymized input here instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Seed for the rewrite, random unless given
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Solve a day on every input file in a directory
+    Batch {
+        day: u32,
+        /// Directory of input files, one per person, by default
+        /// `<fixtures>/<year>/day_<NN>`
+        dir: Option<PathBuf>,
+        /// Defaults to the latest year with solutions
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Print the table as CSV
+        #[arg(long)]
+        csv: bool,
+        /// Neither read nor store cached answers
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Solve a day, or every day, on its puzzle input
+    Run {
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u32>,
+        /// Defaults to the latest year with solutions
+        #[arg(short, long, conflicts_with = "all")]
+        year: Option<u32>,
+        /// Run every registered puzzle that has an input
+        #[arg(short, long)]
+        all: bool,
+        /// Puzzle input file instead of the one in the inputs directory
+        #[arg(short, long, value_name = "FILE", conflicts_with = "all")]
+        input: Option<PathBuf>,
+        /// Defaults to the format in the config
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+        /// Explain step by step how the answers were reached, for the ITEMth
+        /// item of the input or for all of them
+        #[arg(long, value_name = "ITEM", num_args = 0..=1, conflicts_with_all = ["all", "format"])]
+        explain: Option<Option<usize>>,
+        /// Neither read nor store cached answers
+        #[arg(long)]
+        no_cache: bool,
+        /// Add the answers and timings of solved parts to the run history
+        #[arg(long, conflicts_with = "explain")]
+        record: bool,
+    },
+    /// Print the settings in effect and where they came from
+    Config,
+    /// Browse every day in the terminal, running, benchmarking and explaining
+    /// them
+    Dashboard {
+        /// Neither read nor store cached answers
+        #[arg(long)]
+        no_cache: bool,
+        /// Add the answers and timings of solved parts to the run history
+        #[arg(long)]
+        record: bool,
+    },
+    /// Manage the cache of answers
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Show how a day's answers and timings changed across commits
+    History {
+        day: u32,
+        /// Defaults to the latest year with solutions
+        #[arg(short, long)]
+        year: Option<u32>,
+    },
+    /// Guess which day an unlabeled input file belongs to
+    ///
+    /// Days are told apart by what their parsers make of the file, so days
+    /// that read their input without parsing it, like 2023 day 1, are never
+    /// suggested.
+    Identify { file: PathBuf },
+    /// Encrypt inputs so they can be committed, or decrypt them back
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommand,
+    },
+    /// Report on a private leaderboard exported as JSON
+    Leaderboard {
+        /// Defaults to `<fixtures>/leaderboard.json`
+        file: Option<PathBuf>,
+        /// Only print this report
+        #[arg(short, long, value_enum)]
+        report: Option<leaderboard::Report>,
+        /// Print the report as CSV
+        #[arg(long, requires = "report")]
+        csv: bool,
+    },
+    /// List the day plugins that were loaded, or why they were not
+    Plugins,
+    /// Print what a day's parser makes of an input
+    Parse {
+        day: u32,
+        /// Defaults to the latest year with solutions
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Puzzle input file instead of the one in the inputs directory
+        #[arg(short, long, value_name = "FILE", conflicts_with = "example")]
+        input: Option<PathBuf>,
+        /// Parse the worked example from the puzzle text instead of an input
+        #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1")]
+        example: Option<usize>,
+        /// Format of the parsed structure
+        #[arg(long, value_enum, default_value_t = dump::DumpFormat::Json)]
+        dump: dump::DumpFormat,
+    },
+    /// Update the progress table between the markers of a README
+    Readme {
+        /// Defaults to the README beside `aoc.toml`
+        readme: Option<PathBuf>,
+        /// Neither read nor store cached answers
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Time each part on generated inputs of growing size
+    Scale {
+        day: u32,
+        /// Defaults to the latest year with solutions
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Add the fastest time of each part at each size to the run history
+        #[arg(long)]
+        record: bool,
+    },
+    /// Solve puzzles posted to `/solve/<year>/<day>` on a local HTTP server
+    Serve {
+        #[arg(short, long, default_value_t = serve::DEFAULT_PORT)]
+        port: u16,
+        /// Neither read nor store cached answers
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Check a day's answers, or every day's, against the accepted ones
+    Verify {
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u32>,
+        /// Defaults to the latest year with solutions
+        #[arg(short, long, conflicts_with = "all")]
+        year: Option<u32>,
+        /// Verify every registered puzzle that has an input
+        #[arg(short, long)]
+        all: bool,
+        /// Neither read nor store cached answers
+        #[arg(long)]
+        no_cache: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Remove every cached answer
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum InputsCommand {
+    /// Encrypt the inputs directory into `sealed/`, creating a key if needed
+    Seal,
+    /// Decrypt `sealed/` into the inputs directory
+    Unseal {
+        /// Replace inputs that differ from their sealed version
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // The dashboard shows the log itself.
+    if !matches!(cli.command, Command::Dashboard { .. }) {
+        logging::init(cli.verbose);
+    }
+    let config = Config::from_env(cli.config.as_deref())?;
+    let timeout = run::timeout(cli.timeout.unwrap_or(config.timeout));
+    let store = || config.store(cli.inputs.clone());
+
+    // Only commands that solve see plugin days, so a broken plugin cannot get
+    // in the way of anything else.
+    let plugins = match &config.plugins {
+        Some(dir) if solves(&cli.command) || matches!(cli.command, Command::Plugins) => {
+            plugins::load_dir(dir)?
+        }
+        _ => vec![],
+    };
+    if !matches!(cli.command, Command::Plugins) {
+        for (path, loaded) in &plugins {
+            if let Err(e) = loaded {
+                eprintln!("Skipping plugin {}: {e:#}", path.display());
+            }
+        }
+    }
+
+    match cli.command {
+        Command::List => {
+            for (year, puzzles) in aoc_registry::years() {
+                println!("{year}");
+                for puzzle in puzzles {
+                    println!("  {:>2}  {}", puzzle.day, puzzle.title);
+                }
+            }
+
+            Ok(())
+        }
+        Command::Anonymize {
+            day,
+            year,
+            input,
+            output,
+            seed,
+        } => {
+            let puzzle = aoc_registry::find(resolve_year(year)?, day)?;
+            let original = inputs::read_input(puzzle, input.as_deref(), &store()?)?;
+            let seed = seed.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(1, |elapsed| elapsed.as_nanos() as u64)
+            });
+
+            anonymize::run(puzzle, &original, output.as_deref(), seed, timeout)
+        }
+        Command::Batch {
+            day,
+            dir,
+            year,
+            csv,
+            no_cache,
+        } => {
+            let cache = config.cache(no_cache);
+            let puzzle = aoc_registry::find(resolve_year(year)?, day)?;
+            let dir = dir.unwrap_or_else(|| {
+                config
+                    .fixtures
+                    .join(puzzle.year.to_string())
+                    .join(format!("day_{:02}", puzzle.day))
+            });
+
+            batch::run(puzzle, &dir, csv, cache.as_ref(), timeout)
+        }
+        Command::Run {
+            day,
+            year,
+            all,
+            input,
+            format,
+            explain,
+            no_cache,
+            record,
+        } => {
+            let cache = config.cache(no_cache);
+            let history = record.then(|| config.history()).transpose()?;
+            let store = store()?;
+            let format = format.unwrap_or(config.format);
+
+            if all {
+                for puzzle in aoc_registry::years().into_values().flatten() {
+                    match store.input(puzzle)? {
+                        Some(input) => run::run(
+                            puzzle,
+                            &input,
+                            cache.as_ref(),
+                            history.as_ref(),
+                            timeout,
+                            format,
+                        )?,
+                        None if format == Format::Json => {}
+                        None => println!("{} {:>2}  no input", puzzle.year, puzzle.day),
+                    }
+                }
+
+                Ok(())
+            } else {
+                let puzzle = aoc_registry::find(resolve_year(year)?, day.unwrap())?;
+                let input = inputs::read_input(puzzle, input.as_deref(), &store)?;
+
+                match explain {
+                    Some(item) => explain::run(puzzle, &input, item),
+                    None => run::run(
+                        puzzle,
+                        &input,
+                        cache.as_ref(),
+                        history.as_ref(),
+                        timeout,
+                        format,
+                    ),
+                }
+            }
+        }
+        Command::Config => {
+            config.print();
+            Ok(())
+        }
+        Command::Dashboard { no_cache, record } => dashboard::run(
+            &store()?,
+            config.cache(no_cache),
+            record.then(|| config.history()).transpose()?,
+            timeout,
+            config.repetitions,
+            cli.verbose,
+        ),
+        Command::Cache {
+            command: CacheCommand::Clear,
+        } => {
+            let removed = config.cache(false).unwrap().clear()?;
+            println!("Removed {removed} cached answers");
+
+            Ok(())
+        }
+        Command::History { day, year } => {
+            let puzzle = aoc_registry::find(resolve_year(year)?, day)?;
+            history::run(&history::History::open_read_only(&config.history)?, puzzle)
+        }
+        Command::Identify { file } => identify::run(&file),
+        Command::Inputs { command } => {
+            let store = store()?;
+
+            match command {
+                InputsCommand::Seal => {
+                    let (sealed, unchanged) = inputs::seal(&store, &config.key_file())?;
+                    println!("Sealed {sealed} files, {unchanged} were up to date");
+                }
+                InputsCommand::Unseal { force } => {
+                    let (written, kept) = inputs::unseal(&store, force)?;
+                    println!("Unsealed {written} files, kept {kept}");
+                }
+            }
+
+            Ok(())
+        }
+        Command::Leaderboard { file, report, csv } => {
+            let file = file.unwrap_or_else(|| config.fixtures.join("leaderboard.json"));
+            leaderboard::run(&file, report, csv)
+        }
+        Command::Plugins => {
+            plugins::run(&plugins, config.plugins.as_deref());
+            Ok(())
+        }
+        Command::Parse {
+            day,
+            year,
+            input,
+            example,
+            dump,
+        } => {
+            let puzzle = aoc_registry::find(resolve_year(year)?, day)?;
+
+            match example {
+                Some(n) => dump::run(puzzle, dump::Source::Example(n), dump),
+                None => {
+                    let input = inputs::read_input(puzzle, input.as_deref(), &store()?)?;
+                    dump::run(puzzle, dump::Source::Input(&input), dump)
+                }
+            }
+        }
+        Command::Readme { readme, no_cache } => {
+            let cache = config.cache(no_cache);
+            let readme = readme.unwrap_or_else(|| config.root.join("README.md"));
+            readme::run(&readme, &store()?, cache.as_ref(), timeout)
+        }
+        Command::Scale { day, year, record } => {
+            let puzzle = aoc_registry::find(resolve_year(year)?, day)?;
+            let history = record.then(|| config.history()).transpose()?;
+            scale::run(puzzle, config.repetitions, history.as_ref(), timeout)
+        }
+        Command::Serve { port, no_cache } => {
+            serve::run(port, config.cache(no_cache).as_ref(), timeout)
+        }
+        Command::Verify {
+            day,
+            year,
+            all,
+            no_cache,
+        } => {
+            let cache = config.cache(no_cache);
+            let puzzles = if all {
+                aoc_registry::years().into_values().flatten().collect()
+            } else {
+                vec![aoc_registry::find(resolve_year(year)?, day.unwrap())?]
+            };
+
+            verify::run(&puzzles, &store()?, cache.as_ref(), timeout)
+        }
+    }
+}
+
+/// Whether the command solves puzzles, and so should find plugin days.
+fn solves(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::Anonymize { .. }
+            | Command::Batch { .. }
+            | Command::Dashboard { .. }
+            | Command::Readme { .. }
+            | Command::Run { .. }
+            | Command::Scale { .. }
+            | Command::Serve { .. }
+            | Command::Verify { .. }
+    )
+}
+
+fn resolve_year(year: Option<u32>) -> Result<u32> {
+    year.or(aoc_registry::latest_year())
+        .ok_or(anyhow!("No puzzles have been registered"))
+}
@@ -0,0 +1,454 @@
+use std::{
+    env::consts::DLL_EXTENSION,
+    ffi::{c_char, CStr},
+    fs, mem,
+    path::{Path, PathBuf},
+    ptr, slice,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use libloading::Library;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use aoc_common::{
+    answer::Answer,
+    cancel::{CancelToken, Cancelled},
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+    plugin::{
+        EntryFn, Outcome, Plugin, Status, VersionFn, ABI_VERSION, ENTRY_SYMBOL, VERSION_SYMBOL,
+    },
+    puzzle::{Puzzle, Solution},
+};
+
+use crate::table::Table;
+
+/// Calls into a loaded plugin to solve. The plugin ABI only carries solving,
+/// so dumping, explaining, generating and anonymizing are left to the
+/// built-in solution for the same day, if there is one. Plugins promise that
+/// `solve` can be called from several threads at once.
+struct PluginSolution {
+    plugin: &'static Plugin,
+    built_in: Option<&'static dyn Solution>,
+}
+
+unsafe impl Sync for PluginSolution {}
+
+/// Stands in for a missing built-in solution, with none of the hooks.
+struct NoBuiltIn;
+
+impl Solution for NoBuiltIn {
+    fn part_one(&self, _lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        bail!("There is no built-in solution")
+    }
+
+    fn part_two(&self, _lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+        bail!("There is no built-in solution")
+    }
+}
+
+impl PluginSolution {
+    fn hooks(&self) -> &dyn Solution {
+        self.built_in.unwrap_or(&NoBuiltIn)
+    }
+
+    fn solve(&self, part: u8, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+        let input = lines.join("\n");
+        // What a plugin that never fills in the outcome is taken to mean.
+        let mut outcome = Outcome {
+            status: Status::Failed as u32,
+            text: ptr::null_mut(),
+        };
+
+        unsafe {
+            (self.plugin.solve)(
+                part,
+                input.as_ptr(),
+                input.len(),
+                cancel.flag(),
+                &mut outcome,
+            )
+        };
+        let text = match outcome.text.is_null() {
+            true => String::new(),
+            false => unsafe { CStr::from_ptr(outcome.text) }
+                .to_string_lossy()
+                .into_owned(),
+        };
+        unsafe { (self.plugin.free)(outcome.text) };
+
+        match Status::try_from(outcome.status) {
+            Ok(Status::Solved) => Ok(text.parse()?),
+            Ok(Status::Cancelled) => Err(Cancelled.into()),
+            Ok(Status::Failed) => Err(anyhow!(text)),
+            Err(unknown) => Err(anyhow!(
+                "The plugin answered with status {unknown}, which is not one of the ABI's"
+            )),
+        }
+    }
+}
+
+impl Solution for PluginSolution {
+    fn part_one(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+        self.solve(1, lines, cancel)
+    }
+
+    fn part_two(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+        self.solve(2, lines, cancel)
+    }
+
+    fn dump(&self, lines: &[&str]) -> Result<Value> {
+        self.hooks().dump(lines)
+    }
+
+    fn explain(&self, lines: &[&str], part: u8, item: Option<usize>) -> Result<String> {
+        self.hooks().explain(lines, part, item)
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        self.hooks().generator()
+    }
+
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        self.hooks().anonymizer()
+    }
+}
+
+fn check_version(path: &Path, version: u32) -> Result<()> {
+    if version != ABI_VERSION {
+        bail!(
+            "{} was built for plugin ABI version {version}, but this runner loads version \
+             {ABI_VERSION}. Rebuild it from the same checkout as the runner.",
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Reads a `'static` string out of a plugin, which stays loaded for good.
+unsafe fn text(pointer: *const c_char, what: &str) -> Result<&'static str> {
+    if pointer.is_null() {
+        bail!("The plugin has no {what}");
+    }
+
+    CStr::from_ptr(pointer)
+        .to_str()
+        .with_context(|| format!("The plugin's {what} is not UTF-8"))
+}
+
+/// The puzzle a plugin describes, solved by calling into it, with the other
+/// hooks of the built-in puzzle for the same day.
+///
+/// # Safety
+///
+/// `plugin` has to follow the plugin ABI and stay loaded for good.
+unsafe fn adopt(plugin: &'static Plugin) -> Result<&'static Puzzle> {
+    let examples = match plugin.example_count {
+        0 => vec![],
+        count => slice::from_raw_parts(plugin.examples, count)
+            .iter()
+            .map(|&example| text(example, "example"))
+            .collect::<Result<_>>()?,
+    };
+
+    Ok(Box::leak(Box::new(Puzzle {
+        year: plugin.year,
+        day: plugin.day,
+        title: text(plugin.title, "title")?,
+        examples: Box::leak(examples.into_boxed_slice()),
+        input: Normalization {
+            trim_lines: plugin.trim_lines,
+            drop_trailing_blank_lines: plugin.drop_trailing_blank_lines,
+        },
+        solution: Box::leak(Box::new(PluginSolution {
+            plugin,
+            built_in: aoc_registry::built_in(plugin.year, plugin.day).map(|puzzle| puzzle.solution),
+        })),
+    })))
+}
+
+/// Loads a plugin library and its puzzle, with the fingerprint of the
+/// library. The library is never unloaded, so the puzzle can be used like a
+/// built-in one.
+fn open(path: &Path) -> Result<(&'static Puzzle, String)> {
+    let contents = fs::read(path).with_context(|| format!("Could not read {}", path.display()))?;
+    let library = unsafe { Library::new(path) }
+        .with_context(|| format!("Could not load {}", path.display()))?;
+
+    let version = unsafe { library.get::<VersionFn>(VERSION_SYMBOL.as_bytes()) }.map_err(|_| {
+        anyhow!(
+            "{} is not an aoc plugin, it has no {VERSION_SYMBOL} function",
+            path.display()
+        )
+    })?;
+    check_version(path, unsafe { version() })?;
+
+    let entry = unsafe { library.get::<EntryFn>(ENTRY_SYMBOL.as_bytes()) }
+        .with_context(|| format!("{} has no {ENTRY_SYMBOL} function", path.display()))?;
+    let plugin = unsafe { entry() };
+    if plugin.is_null() {
+        bail!("{} did not describe its puzzle", path.display());
+    }
+
+    // Never unloaded, so whatever the plugin points to stays valid.
+    mem::forget(library);
+    let puzzle = unsafe { adopt(&*plugin) }
+        .with_context(|| format!("{} is not a valid plugin", path.display()))?;
+
+    Ok((puzzle, format!("{:x}", Sha256::digest(contents))))
+}
+
+/// Loads a plugin library and registers its puzzle.
+pub fn load(path: &Path) -> Result<&'static Puzzle> {
+    let (puzzle, fingerprint) = open(path)?;
+    aoc_registry::register(puzzle, Box::leak(fingerprint.into_boxed_str()));
+
+    Ok(puzzle)
+}
+
+/// Loads every library in `dir`, which may not exist. A plugin that cannot be
+/// loaded is left out, with the reason why.
+pub fn load_dir(dir: &Path) -> Result<Vec<(PathBuf, Result<&'static Puzzle>)>> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut files = vec![];
+    for entry in fs::read_dir(dir).with_context(|| format!("Could not read {}", dir.display()))? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == DLL_EXTENSION)
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files
+        .into_iter()
+        .map(|path| {
+            let loaded = load(&path);
+            (path, loaded)
+        })
+        .collect())
+}
+
+pub fn run(loaded: &[(PathBuf, Result<&'static Puzzle>)], dir: Option<&Path>) {
+    let Some(dir) = dir else {
+        println!("No plugins directory is set, give one as plugins in aoc.toml or AOC_PLUGINS");
+        return;
+    };
+    if loaded.is_empty() {
+        println!("No plugins in {}", dir.display());
+        return;
+    }
+
+    let rows = loaded
+        .iter()
+        .map(|(path, loaded)| {
+            let file = path.file_name().unwrap_or_default().to_string_lossy();
+            match loaded {
+                Ok(puzzle) => vec![
+                    file.into_owned(),
+                    puzzle.year.to_string(),
+                    puzzle.day.to_string(),
+                    puzzle.title.to_string(),
+                    String::new(),
+                ],
+                Err(e) => vec![
+                    file.into_owned(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    format!("{e:#}"),
+                ],
+            }
+        })
+        .collect();
+
+    let table = Table {
+        headers: vec!["File", "Year", "Day", "Title", "Problem"],
+        rows,
+    };
+    print!("{}", table.to_text());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::{self, consts},
+        process::Command,
+        sync::atomic::AtomicBool,
+    };
+
+    use aoc_common::{input, plugin};
+
+    use super::*;
+
+    #[test]
+    fn test_exported_puzzle_round_trips() {
+        let built_in = aoc_registry::find(2023, 6).unwrap();
+        let puzzle = unsafe { adopt(&*plugin::export(built_in)) }.unwrap();
+
+        assert_eq!(
+            (puzzle.year, puzzle.day, puzzle.title),
+            (2023, 6, built_in.title)
+        );
+        assert_eq!(puzzle.examples, built_in.examples);
+        assert_eq!(puzzle.input, built_in.input);
+
+        let lines = input::lines(puzzle.examples[0], puzzle.input);
+        let cancel = CancelToken::new();
+        assert_eq!(
+            puzzle
+                .solution
+                .part_one(&lines, &cancel)
+                .unwrap()
+                .to_string(),
+            "288"
+        );
+        assert_eq!(
+            puzzle
+                .solution
+                .part_two(&lines, &cancel)
+                .unwrap()
+                .to_string(),
+            "71503"
+        );
+
+        let error = puzzle
+            .solution
+            .part_one(&["Time: seven", "Distance: 9"], &cancel)
+            .unwrap_err();
+        assert!(!error.is::<Cancelled>());
+    }
+
+    // Builds a day as a plugin, the way the README says to, in a target
+    // directory of its own so as not to wait on the one the tests run from.
+    fn build_plugin(package: &str) -> PathBuf {
+        let exe = env::current_exe().unwrap();
+        let target_dir = exe.ancestors().nth(3).unwrap().join("plugins");
+        let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+        let built = Command::new(cargo)
+            .args([
+                "rustc",
+                "--quiet",
+                "-p",
+                package,
+                "--lib",
+                "--features",
+                "plugin",
+            ])
+            .args(["--crate-type", "cdylib", "--target-dir"])
+            .arg(&target_dir)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .status()
+            .unwrap();
+        assert!(built.success(), "could not build {package} as a plugin");
+
+        let library = format!("{}{package}{}", consts::DLL_PREFIX, consts::DLL_SUFFIX);
+        target_dir.join("debug").join(library)
+    }
+
+    #[test]
+    fn test_plugin_keeps_the_built_in_hooks() {
+        let (puzzle, _) = open(&build_plugin("day_9_mirage_maintenance")).unwrap();
+        let built_in = aoc_registry::built_in(2023, 9).unwrap();
+        let lines = input::lines(puzzle.examples[0], puzzle.input);
+        let cancel = CancelToken::new();
+
+        assert_eq!((puzzle.year, puzzle.day), (2023, 9));
+        assert_eq!(
+            puzzle
+                .solution
+                .part_two(&lines, &cancel)
+                .unwrap()
+                .to_string(),
+            "2"
+        );
+        assert_eq!(
+            puzzle.solution.explain(&lines, 1, Some(1)).unwrap(),
+            built_in.solution.explain(&lines, 1, Some(1)).unwrap()
+        );
+        assert_eq!(
+            puzzle.solution.dump(&lines).unwrap(),
+            built_in.solution.dump(&lines).unwrap()
+        );
+        assert!(puzzle.solution.generator().is_some());
+        assert!(puzzle.solution.anonymizer().is_some());
+    }
+
+    unsafe extern "C" fn unknown_status(
+        _part: u8,
+        _input: *const u8,
+        _input_len: usize,
+        _cancelled: *const AtomicBool,
+        outcome: *mut Outcome,
+    ) {
+        ptr::write(
+            outcome,
+            Outcome {
+                status: 7,
+                text: ptr::null_mut(),
+            },
+        );
+    }
+
+    unsafe extern "C" fn free_nothing(_text: *mut c_char) {}
+
+    #[test]
+    fn test_unknown_status_is_an_error() {
+        let plugin = Box::leak(Box::new(Plugin {
+            year: 2023,
+            day: 6,
+            title: ptr::null(),
+            examples: ptr::null(),
+            example_count: 0,
+            trim_lines: true,
+            drop_trailing_blank_lines: true,
+            solve: unknown_status,
+            free: free_nothing,
+        }));
+
+        let solution = PluginSolution {
+            plugin,
+            built_in: None,
+        };
+        let error = solution.solve(1, &[], &CancelToken::new()).unwrap_err();
+        assert!(error.to_string().contains("status 7"));
+    }
+
+    #[test]
+    fn test_version_mismatch_is_explained() {
+        assert!(check_version(Path::new("day.so"), ABI_VERSION).is_ok());
+
+        let error = check_version(Path::new("day.so"), ABI_VERSION + 1).unwrap_err();
+        assert!(error.to_string().starts_with(&format!(
+            "day.so was built for plugin ABI version {}, but this runner loads version {ABI_VERSION}",
+            ABI_VERSION + 1
+        )));
+    }
+
+    #[test]
+    fn test_only_libraries_are_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("README.md"), "").unwrap();
+        fs::write(
+            dir.path().join(format!("broken.{DLL_EXTENSION}")),
+            "not a library",
+        )
+        .unwrap();
+
+        let loaded = load_dir(dir.path()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        let Err(e) = &loaded[0].1 else {
+            panic!("loaded a file that is not a library");
+        };
+        assert!(format!("{e:#}").starts_with("Could not load"));
+        assert!(load_dir(&dir.path().join("missing")).unwrap().is_empty());
+    }
+}
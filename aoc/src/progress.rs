@@ -0,0 +1,160 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+use aoc_common::progress::ProgressHook;
+
+const WIDTH: usize = 24;
+// Loops that finish quickly never get a bar, and a slow one is redrawn a few
+// times a second rather than on every update.
+const DELAY: Duration = Duration::from_millis(250);
+const REFRESH: Duration = Duration::from_millis(100);
+
+// Set once something else draws on the terminal, which a bar would garble.
+static SUPPRESSED: AtomicBool = AtomicBool::new(false);
+
+/// A progress bar on stderr, when stderr is a terminal. Otherwise progress
+/// goes unreported.
+pub fn hook() -> Option<Arc<dyn ProgressHook>> {
+    (!SUPPRESSED.load(Ordering::Relaxed) && io::stderr().is_terminal())
+        .then(|| Arc::new(Bar::default()) as Arc<dyn ProgressHook>)
+}
+
+/// Stops any more bars from being drawn.
+pub fn suppress() {
+    SUPPRESSED.store(true, Ordering::Relaxed);
+}
+
+#[derive(Default)]
+struct Bar {
+    state: Mutex<Option<State>>,
+}
+
+struct State {
+    label: String,
+    total: Option<u64>,
+    started: Instant,
+    drawn: Option<Instant>,
+}
+
+impl ProgressHook for Bar {
+    fn start(&self, label: &str, total: Option<u64>) {
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner) = Some(State {
+            label: label.to_string(),
+            total,
+            started: Instant::now(),
+            drawn: None,
+        });
+    }
+
+    fn update(&self, done: u64) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+
+        let now = Instant::now();
+        let elapsed = now - state.started;
+        if elapsed < DELAY || state.drawn.is_some_and(|drawn| now - drawn < REFRESH) {
+            return;
+        }
+
+        let line = line(&state.label, done, state.total, elapsed);
+        let _ = write!(io::stderr(), "\r\x1b[2K{line}");
+        state.drawn = Some(now);
+    }
+
+    fn finish(&self) {
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        if state.is_some_and(|state| state.drawn.is_some()) {
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+/// One line of the bar, e.g. `seeds [=======>      ] 53%  1.21M/s  ETA 0:07`.
+fn line(label: &str, done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    let rate = done as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+
+    match total {
+        Some(total) if total > 0 => {
+            let fraction = (done as f64 / total as f64).min(1.0);
+            let filled = (fraction * WIDTH as f64) as usize;
+            let bar = if filled < WIDTH {
+                format!("{}>{}", "=".repeat(filled), " ".repeat(WIDTH - filled - 1))
+            } else {
+                "=".repeat(WIDTH)
+            };
+            let eta = (rate > 0.0).then(|| (total - done.min(total)) as f64 / rate);
+
+            format!(
+                "{label} [{bar}] {:>3.0}%  {}/s  ETA {}",
+                fraction * 100.0,
+                si(rate),
+                eta.map_or("?".to_string(), clock)
+            )
+        }
+        _ => format!("{label} {} steps  {}/s", si(done as f64), si(rate)),
+    }
+}
+
+fn si(value: f64) -> String {
+    match value {
+        v if v >= 1e9 => format!("{:.2}G", v / 1e9),
+        v if v >= 1e6 => format!("{:.2}M", v / 1e6),
+        v if v >= 1e3 => format!("{:.2}k", v / 1e3),
+        v => format!("{v:.0}"),
+    }
+}
+
+fn clock(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+
+    if seconds >= 3_600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3_600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_with_total() {
+        assert_eq!(
+            line("seeds", 500, Some(1000), Duration::from_secs(2)),
+            format!(
+                "seeds [{}>{}]  50%  250/s  ETA 0:02",
+                "=".repeat(12),
+                " ".repeat(11)
+            )
+        );
+        assert!(line("seeds", 1000, Some(1000), Duration::from_secs(1)).contains("100%"));
+    }
+
+    #[test]
+    fn test_line_without_total() {
+        assert_eq!(
+            line("AAA", 2_500_000, None, Duration::from_secs(2)),
+            "AAA 2.50M steps  1.25M/s"
+        );
+        assert_eq!(clock(3_725.0), "1:02:05");
+    }
+}
@@ -0,0 +1,189 @@
+use std::{fs, path::Path, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use tracing::warn;
+
+use aoc_common::{input, puzzle::Puzzle};
+
+use crate::{
+    cache::Cache,
+    inputs::Store,
+    run,
+    verify::{self, Verdict},
+};
+
+pub const START_MARKER: &str = "<!-- progress:start -->";
+pub const END_MARKER: &str = "<!-- progress:end -->";
+
+pub struct Row {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub stars: usize,
+    pub times: [String; 2],
+}
+
+pub fn progress_row(
+    puzzle: &'static Puzzle,
+    store: &Store,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<Row> {
+    let mut row = Row {
+        year: puzzle.year,
+        day: puzzle.day,
+        title: puzzle.title,
+        stars: 0,
+        times: ["–".to_string(), "–".to_string()],
+    };
+
+    let Some(input) = store.input(puzzle)? else {
+        return Ok(row);
+    };
+    let lines = input::lines(&input, puzzle.input);
+    let expected = verify::expected_answers(store, puzzle)?;
+
+    for part in 1..=2 {
+        let index = part as usize - 1;
+
+        match run::solve(puzzle, part, &input, &lines, cache, timeout) {
+            Ok(run::Part { solved, .. }) => {
+                let answer = &solved.answer;
+                row.times[index] = format!("{:.2?}", Duration::from_secs_f64(solved.seconds));
+
+                match verify::verdict(expected[index].as_ref(), answer) {
+                    Verdict::Correct => row.stars += 1,
+                    Verdict::Wrong { expected } => {
+                        warn!(%answer, %expected, part, day = puzzle.day, "wrong answer");
+                    }
+                    Verdict::Unknown => {}
+                }
+            }
+            Err(e) if e.is::<run::TimedOut>() => {
+                warn!(error = %e, "gave up on solving");
+                row.times[index] = "timed out".to_string();
+            }
+            Err(e) => {
+                warn!(error = %e, "could not solve");
+                row.times[index] = "failed".to_string();
+            }
+        }
+    }
+
+    Ok(row)
+}
+
+pub fn markdown(rows: &[Row]) -> String {
+    let mut table = "| Year | Day | Title | Stars | Part 1 | Part 2 |\n\
+                     |------|----:|-------|-------|-------:|-------:|\n"
+        .to_string();
+
+    for row in rows {
+        table += &format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            row.year,
+            row.day,
+            row.title.replace('|', "\\|"),
+            "★".repeat(row.stars),
+            row.times[0],
+            row.times[1]
+        );
+    }
+
+    table
+}
+
+/// Replaces whatever is between the progress markers of `readme` with `table`.
+pub fn splice(readme: &str, table: &str) -> Result<String> {
+    let missing = || anyhow!("The README needs a `{START_MARKER}` line followed by `{END_MARKER}`");
+
+    let start = readme.find(START_MARKER).ok_or_else(missing)? + START_MARKER.len();
+    let end = start + readme[start..].find(END_MARKER).ok_or_else(missing)?;
+
+    Ok(format!("{}\n{table}{}", &readme[..start], &readme[end..]))
+}
+
+pub fn run(
+    readme: &Path,
+    store: &Store,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let contents = fs::read_to_string(readme)
+        .with_context(|| format!("Could not read {}", readme.display()))?;
+
+    let rows = aoc_registry::years()
+        .into_values()
+        .flatten()
+        .map(|puzzle| progress_row(puzzle, store, cache, timeout))
+        .collect::<Result<Vec<Row>>>()?;
+    let updated = splice(&contents, &markdown(&rows))?;
+
+    if updated == contents {
+        println!("{} is up to date", readme.display());
+    } else {
+        fs::write(readme, updated)
+            .with_context(|| format!("Could not write {}", readme.display()))?;
+        println!("Updated {}", readme.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs;
+
+    #[test]
+    fn test_splice() {
+        let readme = format!("# Advent\n\n{START_MARKER}\nold table\n{END_MARKER}\n\nMore\n");
+
+        assert_eq!(
+            splice(&readme, "| new |\n").unwrap(),
+            format!("# Advent\n\n{START_MARKER}\n| new |\n{END_MARKER}\n\nMore\n")
+        );
+        assert!(splice("# Advent\n", "| new |\n").is_err());
+        assert!(splice(&format!("{END_MARKER}\n{START_MARKER}\n"), "| new |\n").is_err());
+    }
+
+    #[test]
+    fn test_progress_row_counts_verified_stars() {
+        let dir = tempfile::tempdir().unwrap();
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let store = Store {
+            plain: dir.path().to_path_buf(),
+            sealed: dir.path().join("sealed"),
+            key: None,
+            answers_file: None,
+        };
+        let input_path = dir.path().join(inputs::input_name(puzzle));
+        fs::create_dir_all(input_path.parent().unwrap()).unwrap();
+        fs::write(&input_path, puzzle.examples[0]).unwrap();
+
+        fs::write(dir.path().join(inputs::answers_name(puzzle)), "288\n1\n").unwrap();
+        let row = progress_row(puzzle, &store, None, None).unwrap();
+        assert_eq!(row.stars, 1);
+        assert_ne!(row.times[1], "–");
+
+        let row = progress_row(aoc_registry::find(2023, 7).unwrap(), &store, None, None).unwrap();
+        assert_eq!(row.stars, 0);
+        assert_eq!(row.times, ["–", "–"]);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let rows = [Row {
+            year: 2023,
+            day: 1,
+            title: "Trebuchet?!",
+            stars: 2,
+            times: ["1.00ms".to_string(), "2.50ms".to_string()],
+        }];
+
+        assert_eq!(
+            markdown(&rows).lines().last().unwrap(),
+            "| 2023 | 1 | Trebuchet?! | ★★ | 1.00ms | 2.50ms |"
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError, TryRecvError},
+        Arc, Mutex, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use tracing::{info, info_span, warn};
+
+use aoc_common::{answer::Answer, cancel::CancelToken, cli::Format, input, puzzle::Puzzle};
+
+use crate::{
+    cache::{Cache, Solved},
+    history::{History, Kind},
+    progress,
+};
+
+pub const DEFAULT_TIMEOUT_SECONDS: f64 = 60.0;
+
+// How long a cancelled part gets to notice before it is left running on its
+// own. Parts that never check their token are only ever stopped this way.
+const GRACE: Duration = Duration::from_secs(1);
+
+// Parts that were given up on and are still running.
+static ABANDONED: AtomicUsize = AtomicUsize::new(0);
+
+/// A part ran out of its time budget and was cancelled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TimedOut(pub Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timed out after {:.2?}", self.0)
+    }
+}
+
+impl Error for TimedOut {}
+
+/// How many parts were left running after they timed out and have not
+/// finished since.
+pub fn abandoned_parts() -> usize {
+    ABANDONED.load(Ordering::Relaxed)
+}
+
+/// The budget for `seconds`, where zero means no limit.
+pub fn timeout(seconds: f64) -> Option<Duration> {
+    (seconds > 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+fn solve_part(puzzle: &Puzzle, part: u8, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+    if part == 1 {
+        puzzle.solution.part_one(lines, cancel)
+    } else {
+        puzzle.solution.part_two(lines, cancel)
+    }
+}
+
+/// Held by a part's thread until it ends, however it ends.
+struct Running(Arc<Mutex<bool>>);
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        if *self.0.lock().unwrap_or_else(PoisonError::into_inner) {
+            ABANDONED.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Solves the part, timing only the solution, on its own thread that is
+/// cancelled once `timeout` has gone by. The thread may outlive the call, so
+/// it gets a copy of the lines.
+pub(crate) fn solve_within(
+    puzzle: &'static Puzzle,
+    part: u8,
+    lines: &[&str],
+    timeout: Option<Duration>,
+) -> Result<Solved> {
+    let cancel = match progress::hook() {
+        Some(hook) => CancelToken::new().with_progress(hook),
+        None => CancelToken::new(),
+    };
+    let timed = move |lines: &[&str], cancel: &CancelToken| -> Result<Solved> {
+        let start = Instant::now();
+        let answer = solve_part(puzzle, part, lines, cancel)?;
+
+        Ok(Solved {
+            answer,
+            seconds: start.elapsed().as_secs_f64(),
+        })
+    };
+    let Some(timeout) = timeout else {
+        return timed(lines, &cancel);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let owned: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    let worker_cancel = cancel.clone();
+    // Whether the part has been given up on. It is only set while the part
+    // has not answered, under the lock its thread takes on the way out.
+    let abandoned = Arc::new(Mutex::new(false));
+    let worker_abandoned = abandoned.clone();
+    thread::Builder::new()
+        .name(format!("day {} part {part}", puzzle.day))
+        .spawn(move || {
+            let _running = Running(worker_abandoned);
+            // Dropped before `_running`, so that the part is seen to be done.
+            let sender = sender;
+            let lines: Vec<&str> = owned.iter().map(String::as_str).collect();
+            // The receiver is gone once the part has been given up on.
+            let _ = sender.send(timed(&lines, &worker_cancel));
+        })?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(solved) => solved,
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            if receiver.recv_timeout(GRACE).is_err() {
+                let mut abandoned = abandoned.lock().unwrap_or_else(PoisonError::into_inner);
+                if let Err(TryRecvError::Empty) = receiver.try_recv() {
+                    warn!(part, "part did not stop when cancelled, leaving it running");
+                    *abandoned = true;
+                    ABANDONED.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+
+            Err(TimedOut(timeout).into())
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(anyhow!("panicked")),
+    }
+}
+
+pub struct Part {
+    pub solved: Solved,
+    pub cached: bool,
+}
+
+/// Solves one part, or takes its answer from the cache when the input and
+/// solution are unchanged since it was last solved. A part that runs past
+/// `timeout` fails with [`TimedOut`].
+pub fn solve(
+    puzzle: &'static Puzzle,
+    part: u8,
+    input: &str,
+    lines: &[&str],
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<Part> {
+    let key = Cache::key(puzzle, part, input);
+
+    if let Some(solved) = cache.and_then(|cache| cache.get(&key)) {
+        info!(part, "answer found in cache");
+        return Ok(Part {
+            solved,
+            cached: true,
+        });
+    }
+
+    let solved = solve_within(puzzle, part, lines, timeout)
+        .with_context(|| format!("{} part {part} failed", puzzle.name()))?;
+    if let Some(cache) = cache {
+        cache.put(&key, &solved)?;
+    }
+
+    Ok(Part {
+        solved,
+        cached: false,
+    })
+}
+
+/// A part's outcome as JSON, the same for `run --format json` and `serve`.
+pub fn part_json(result: &Result<Part>) -> Value {
+    match result {
+        Ok(Part { solved, cached }) => json!({
+            "answer": solved.answer,
+            "seconds": solved.seconds,
+            "cached": cached,
+        }),
+        Err(e) => match e.downcast_ref::<TimedOut>() {
+            Some(TimedOut(after)) => json!({"timed_out": after.as_secs_f64()}),
+            None => json!({"error": format!("{e:#}")}),
+        },
+    }
+}
+
+/// Solves both parts and prints them, as text or as one JSON object per
+/// puzzle so that `--all` gives JSON lines. Parts that were solved rather than
+/// taken from the cache are recorded in `history`.
+pub fn run(
+    puzzle: &'static Puzzle,
+    input: &str,
+    cache: Option<&Cache>,
+    history: Option<&History>,
+    timeout: Option<Duration>,
+    format: Format,
+) -> Result<()> {
+    let _span = info_span!("run", year = puzzle.year, day = puzzle.day).entered();
+
+    let lines = input::lines(input, puzzle.input);
+    let mut parts = vec![];
+
+    // A part that times out is reported, while any other failure stops the run.
+    for part in 1..=2 {
+        let solved = solve(puzzle, part, input, &lines, cache, timeout);
+        if solved
+            .as_ref()
+            .is_err_and(|e| e.downcast_ref::<TimedOut>().is_none())
+        {
+            return solved.map(|_| ());
+        }
+        if let (
+            Some(history),
+            Ok(Part {
+                solved,
+                cached: false,
+            }),
+        ) = (history, &solved)
+        {
+            history.record(puzzle, part, input, solved, Kind::Run)?;
+        }
+        parts.push(solved);
+    }
+
+    match format {
+        Format::Text => {
+            println!("{} {:>2}  {}", puzzle.year, puzzle.day, puzzle.title);
+
+            for (part, result) in (1..).zip(&parts) {
+                match result {
+                    Ok(Part { solved, cached }) => {
+                        let elapsed = Duration::from_secs_f64(solved.seconds);
+                        let cached = if *cached { ", cached" } else { "" };
+                        println!(
+                            "         Part {part}: {} ({elapsed:.2?}{cached})",
+                            solved.answer
+                        );
+                    }
+                    Err(e) => println!("         Part {part}: {}", e.root_cause()),
+                }
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            json!({
+                "year": puzzle.year,
+                "day": puzzle.day,
+                "title": puzzle.title,
+                "part_one": part_json(&parts[0]),
+                "part_two": part_json(&parts[1]),
+            })
+        ),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_fills_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let input = puzzle.examples[0];
+
+        let cache = Cache::new(dir.path().join("cache"));
+        run(puzzle, input, Some(&cache), None, None, Format::Text).unwrap();
+
+        let solved = cache.get(&Cache::key(puzzle, 2, input)).unwrap();
+        assert_eq!(solved.answer.to_string(), "71503");
+    }
+
+    #[test]
+    fn test_only_solved_parts_are_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let puzzle = aoc_registry::find(2023, 6).unwrap();
+        let input = puzzle.examples[0];
+        let cache = Cache::new(dir.path().join("cache"));
+        let history = History::open(&dir.path().join("history.sqlite"), "abc".into()).unwrap();
+
+        for _ in 0..2 {
+            run(
+                puzzle,
+                input,
+                Some(&cache),
+                Some(&history),
+                None,
+                Format::Text,
+            )
+            .unwrap();
+        }
+
+        let records = history.records(puzzle).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].answer, "71503");
+    }
+
+    #[test]
+    fn test_endless_part_times_out() {
+        let puzzle = aoc_registry::find(2023, 8).unwrap();
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n";
+        let lines = input::lines(input, puzzle.input);
+        let timeout = Duration::from_millis(50);
+
+        let error = solve(puzzle, 1, input, &lines, None, Some(timeout))
+            .err()
+            .unwrap();
+        assert_eq!(error.downcast_ref(), Some(&TimedOut(timeout)));
+        assert_eq!(error.root_cause().to_string(), "timed out after 50.00ms");
+    }
+
+    struct Stubborn;
+
+    impl aoc_common::puzzle::Solution for Stubborn {
+        fn part_one(&self, _lines: &[&str], _cancel: &CancelToken) -> Result<Answer> {
+            thread::sleep(GRACE + Duration::from_millis(200));
+            Ok(Answer::from(1_u32))
+        }
+
+        fn part_two(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer> {
+            self.part_one(lines, cancel)
+        }
+    }
+
+    static STUBBORN: Puzzle = Puzzle {
+        year: 2023,
+        day: 99,
+        title: "Stubborn",
+        examples: &[],
+        input: input::Normalization::TRIM,
+        solution: &Stubborn,
+    };
+
+    #[test]
+    fn test_abandoned_parts_are_counted_until_they_end() {
+        let timeout = Duration::from_millis(10);
+
+        let error = solve_within(&STUBBORN, 1, &[], Some(timeout)).unwrap_err();
+        assert!(error.is::<TimedOut>());
+        assert_eq!(abandoned_parts(), 1);
+
+        thread::sleep(Duration::from_millis(500));
+        assert_eq!(abandoned_parts(), 0);
+    }
+}
@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use tracing::{debug, info_span};
+
+use aoc_common::{generate::Rng, input, puzzle::Puzzle};
+
+use crate::{
+    cache::Solved,
+    history::{History, Kind},
+    run,
+};
+
+const STEPS: u32 = 6;
+const SEED: u64 = 2023;
+
+// Anything growing faster than this is flagged as an accidentally quadratic
+// (or worse) path rather than measurement noise on a linear one.
+const SUPERLINEAR_EXPONENT: f64 = 1.5;
+
+struct Sample {
+    size: usize,
+    part_one: Duration,
+    part_two: Duration,
+}
+
+/// Times both parts on inputs of doubling size, each run within `timeout`,
+/// adding each fastest time to `history`, kept apart from timings on real
+/// inputs.
+pub fn run(
+    puzzle: &'static Puzzle,
+    repetitions: usize,
+    history: Option<&History>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let generator = puzzle.solution.generator().ok_or(anyhow!(
+        "Day {} of {} has no input generator",
+        puzzle.day,
+        puzzle.year
+    ))?;
+
+    println!("{} (size = {})", puzzle.name(), generator.unit);
+    println!("{:>10} {:>14} {:>14}", "size", "part one", "part two");
+
+    let mut rng = Rng::new(SEED);
+    let mut samples = vec![];
+
+    for step in 0..STEPS {
+        let size = generator.base_size << step;
+        let _span = info_span!("scale", size).entered();
+
+        let input = (generator.generate)(size, &mut rng);
+        debug!(bytes = input.len(), "generated input");
+        let lines = input::lines(&input, puzzle.input);
+
+        let solve = |part| run::solve_within(puzzle, part, &lines, timeout);
+        let part_one = time_part(repetitions, || solve(1))
+            .with_context(|| format!("part one failed at size {size}"))?;
+        let part_two = time_part(repetitions, || solve(2))
+            .with_context(|| format!("part two failed at size {size}"))?;
+        if let Some(history) = history {
+            history.record(puzzle, 1, &input, &part_one, Kind::Scale)?;
+            history.record(puzzle, 2, &input, &part_two, Kind::Scale)?;
+        }
+
+        let sample = Sample {
+            size,
+            part_one: Duration::from_secs_f64(part_one.seconds),
+            part_two: Duration::from_secs_f64(part_two.seconds),
+        };
+
+        println!(
+            "{:>10} {:>14.2?} {:>14.2?}",
+            sample.size, sample.part_one, sample.part_two
+        );
+        samples.push(sample);
+    }
+
+    println!(
+        "Growth exponent: part one {}, part two {}",
+        describe_exponent(&samples, |sample| sample.part_one),
+        describe_exponent(&samples, |sample| sample.part_two),
+    );
+
+    Ok(())
+}
+
+/// The answer and fastest time of `repetitions` runs of a part, which is run
+/// at least once.
+pub fn time_part(repetitions: usize, part: impl Fn() -> Result<Solved>) -> Result<Solved> {
+    let mut fastest = part()?;
+
+    for _ in 1..repetitions {
+        fastest.seconds = fastest.seconds.min(part()?.seconds);
+    }
+
+    Ok(fastest)
+}
+
+// Fixed costs dominate the smallest inputs, so only the larger half of the
+// sizes is used to estimate the asymptotic growth.
+fn describe_exponent(samples: &[Sample], timing: fn(&Sample) -> Duration) -> String {
+    let points: Vec<(f64, f64)> = samples[samples.len() / 2..]
+        .iter()
+        .map(|sample| (sample.size as f64, timing(sample).as_secs_f64()))
+        .collect();
+
+    match growth_exponent(&points) {
+        Some(exponent) if exponent > SUPERLINEAR_EXPONENT => {
+            format!("≈ {exponent:.2} (superlinear)")
+        }
+        Some(exponent) => format!("≈ {exponent:.2}"),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Least squares slope of `log(time)` against `log(size)`, i.e. the `k` in
+/// `time ∝ size^k`.
+pub fn growth_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(size, time)| *size > 0.0 && *time > 0.0)
+        .map(|(size, time)| (size.ln(), time.ln()))
+        .collect();
+
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    if variance == 0.0 {
+        None
+    } else {
+        Some(covariance / variance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::cancel::CancelToken;
+    use aoc_registry::years;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let mut rng = Rng::new(2023);
+        let cancel = CancelToken::new();
+
+        for puzzle in &years()[&2023] {
+            let generator = puzzle.solution.generator().unwrap();
+            let input = (generator.generate)(generator.base_size, &mut rng);
+            let lines = input::lines(&input, puzzle.input);
+
+            assert!(
+                puzzle.solution.part_one(&lines, &cancel).is_ok(),
+                "day {} part one",
+                puzzle.day
+            );
+            assert!(
+                puzzle.solution.part_two(&lines, &cancel).is_ok(),
+                "day {} part two",
+                puzzle.day
+            );
+        }
+    }
+
+    #[test]
+    fn test_recorded_as_scale_timings() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(&dir.path().join("history.sqlite"), "abc".into()).unwrap();
+        let puzzle = aoc_registry::find(2023, 9).unwrap();
+
+        run(puzzle, 1, Some(&history), None).unwrap();
+
+        let records = history.records(puzzle).unwrap();
+        assert_eq!(records.len(), 2 * STEPS as usize);
+        assert!(records.iter().all(|record| record.kind == "scale"));
+    }
+
+    #[test]
+    fn test_growth_exponent() {
+        let linear: Vec<(f64, f64)> = (1..6).map(|i| (i as f64, 3.0 * i as f64)).collect();
+        let quadratic: Vec<(f64, f64)> = (1..6).map(|i| (i as f64, (i * i) as f64)).collect();
+
+        assert!((growth_exponent(&linear).unwrap() - 1.0).abs() < 1e-9);
+        assert!((growth_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_growth_exponent_needs_two_sizes() {
+        assert_eq!(growth_exponent(&[(4.0, 1.0)]), None);
+        assert_eq!(growth_exponent(&[(4.0, 1.0), (4.0, 2.0)]), None);
+    }
+}
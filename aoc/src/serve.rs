@@ -0,0 +1,308 @@
+use std::{
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{info, warn};
+
+use aoc_common::input;
+
+use crate::{cache::Cache, run};
+
+pub const DEFAULT_PORT: u16 = 8080;
+
+// Real inputs are a few tens of kilobytes.
+const MAX_INPUT_BYTES: u64 = 16 << 20;
+
+/// Listens on localhost only, as anything that can reach the server can make
+/// it run solutions for as long as the timeout allows.
+pub fn bind(port: u16) -> Result<Server> {
+    Server::http(("127.0.0.1", port)).map_err(|e| anyhow!("Could not listen on port {port}: {e}"))
+}
+
+/// As many requests are answered at once as there are cores.
+fn workers() -> usize {
+    thread::available_parallelism().map_or(4, |n| n.get())
+}
+
+fn error(status: u16, message: impl ToString) -> (u16, Value) {
+    (status, json!({"error": message.to_string()}))
+}
+
+/// Both parts of the puzzle solved on `input`, with the irregularities
+/// normalization found in it.
+fn solve(
+    year: u32,
+    day: u32,
+    input: &str,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> (u16, Value) {
+    let puzzle = match aoc_registry::find(year, day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => return error(404, e),
+    };
+
+    let normalized = input::normalize(input, puzzle.input);
+    let lines = &normalized.lines;
+    let [part_one, part_two] =
+        [1, 2].map(|part| run::part_json(&run::solve(puzzle, part, input, lines, cache, timeout)));
+    let diagnostics: Vec<_> = normalized
+        .irregularities
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    (
+        200,
+        json!({
+            "year": puzzle.year,
+            "day": puzzle.day,
+            "title": puzzle.title,
+            "part_one": part_one,
+            "part_two": part_two,
+            "diagnostics": diagnostics,
+        }),
+    )
+}
+
+/// The status and JSON body answering a request.
+fn route(
+    method: &Method,
+    url: &str,
+    body: &mut dyn Read,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> (u16, Value) {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (method, &segments[..]) {
+        (Method::Get, ["puzzles"]) => {
+            let puzzles: Vec<_> = aoc_registry::years()
+                .into_values()
+                .flatten()
+                .map(
+                    |puzzle| json!({"year": puzzle.year, "day": puzzle.day, "title": puzzle.title}),
+                )
+                .collect();
+
+            (200, json!(puzzles))
+        }
+        (Method::Post, ["solve", year, day]) => {
+            let (Ok(year), Ok(day)) = (year.parse(), day.parse()) else {
+                return error(404, format!("{path} does not name a puzzle"));
+            };
+
+            // Parts that ignore their timeout keep a core busy, so once there
+            // are as many of them as workers nothing new is started.
+            if run::abandoned_parts() >= workers() {
+                return error(503, "Too many timed out parts are still running");
+            }
+
+            let mut input = String::new();
+            match body.take(MAX_INPUT_BYTES + 1).read_to_string(&mut input) {
+                Err(e) => return error(400, format!("The input could not be read: {e}")),
+                Ok(read) if read as u64 > MAX_INPUT_BYTES => {
+                    return error(
+                        413,
+                        format!("Inputs are limited to {} MiB", MAX_INPUT_BYTES >> 20),
+                    )
+                }
+                Ok(_) => {}
+            }
+
+            solve(year, day, &input, cache, timeout)
+        }
+        (_, ["puzzles"] | ["solve", _, _]) => {
+            error(405, format!("{method} is not allowed on {path}"))
+        }
+        _ => error(404, format!("Nothing is served at {path}")),
+    }
+}
+
+/// Answers `request`, with a 500 if solving panicked, which only happens on
+/// the worker itself when there is no timeout.
+fn respond(mut request: Request, cache: Option<&Cache>, timeout: Option<Duration>) {
+    let start = Instant::now();
+    let method = request.method().clone();
+    let url = request.url().to_string();
+
+    let routed = panic::catch_unwind(AssertUnwindSafe(|| {
+        route(&method, &url, request.as_reader(), cache, timeout)
+    }));
+    let (status, body) = routed.unwrap_or_else(|_| error(500, "Solving the puzzle panicked"));
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+
+    info!(%method, url, status, elapsed = ?start.elapsed(), "served");
+    if let Err(e) = request.respond(response) {
+        warn!(%e, "could not send response");
+    }
+}
+
+/// Answers requests until the server is unblocked, on a fixed number of
+/// workers so that a slow puzzle does not hold up the rest while a burst of
+/// requests cannot start more solves than there are cores. Requests beyond
+/// that wait their turn.
+pub fn serve(server: &Server, cache: Option<&Cache>, timeout: Option<Duration>) {
+    let workers = workers();
+    let (sender, receiver) = mpsc::sync_channel::<Request>(workers);
+    let receiver = Mutex::new(receiver);
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                // The lock is only held while waiting for the next request.
+                let next = receiver
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .recv();
+                match next {
+                    Ok(request) => respond(request, cache, timeout),
+                    Err(_) => break,
+                }
+            });
+        }
+
+        for request in server.incoming_requests() {
+            if sender.send(request).is_err() {
+                break;
+            }
+        }
+        drop(sender);
+    });
+}
+
+pub fn run(port: u16, cache: Option<&Cache>, timeout: Option<Duration>) -> Result<()> {
+    let server = bind(port)?;
+    if let Some(address) = server.server_addr().to_ip() {
+        println!("Listening on http://{address}");
+    }
+
+    serve(&server, cache, timeout);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, Write},
+        net::TcpStream,
+    };
+
+    use super::*;
+
+    fn request(port: u16, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_solves_over_http() {
+        let server = bind(0).unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+
+        thread::scope(|scope| {
+            scope.spawn(|| serve(&server, None, Some(Duration::from_secs(10))));
+
+            let puzzle = aoc_registry::find(2023, 6).unwrap();
+            let (status, solved) = request(port, "POST", "/solve/2023/6", puzzle.examples[0]);
+            assert_eq!(status, 200);
+            assert_eq!(solved["part_one"]["answer"], "288");
+            assert_eq!(solved["part_two"]["answer"], "71503");
+            assert_eq!(solved["diagnostics"], json!([]));
+
+            let (status, solved) =
+                request(port, "POST", "/solve/2023/6", "Time: 7\r\nDistance: 9\r\n");
+            assert_eq!(status, 200);
+            assert_eq!(solved["part_one"]["answer"], "4");
+            assert_eq!(solved["diagnostics"], json!(["2 lines end with CRLF"]));
+
+            let (status, solved) = request(port, "POST", "/solve/1999/1", "");
+            assert_eq!(status, 404);
+            assert!(solved["error"].is_string());
+
+            let (status, _) = request(port, "GET", "/solve/2023/6", "");
+            assert_eq!(status, 405);
+
+            let (status, puzzles) = request(port, "GET", "/puzzles", "");
+            assert_eq!(status, 200);
+            assert_eq!(puzzles[0]["title"], "Trebuchet?!");
+
+            server.unblock();
+        });
+    }
+
+    #[test]
+    fn test_oversized_input_is_refused() {
+        let mut body = io::repeat(b'1').take(MAX_INPUT_BYTES + 1);
+        let (status, refused) = route(&Method::Post, "/solve/2023/6", &mut body, None, None);
+
+        assert_eq!(status, 413);
+        assert!(refused["error"].is_string());
+    }
+
+    // Day 6 only overflows, and so panics, when overflow checks are on.
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_panics_do_not_stop_workers() {
+        let server = bind(0).unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+
+        thread::scope(|scope| {
+            scope.spawn(|| serve(&server, None, None));
+
+            for _ in 0..=workers() {
+                let (status, failed) =
+                    request(port, "POST", "/solve/2023/6", "Time: 6\nDistance: 9\n");
+                assert_eq!(status, 500);
+                assert!(failed["error"].is_string());
+            }
+
+            let puzzle = aoc_registry::find(2023, 6).unwrap();
+            let (status, solved) = request(port, "POST", "/solve/2023/6", puzzle.examples[0]);
+            assert_eq!(status, 200);
+            assert_eq!(solved["part_one"]["answer"], "288");
+
+            server.unblock();
+        });
+    }
+
+    #[test]
+    fn test_failed_part_is_reported() {
+        let server = bind(0).unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+
+        thread::scope(|scope| {
+            scope.spawn(|| serve(&server, None, Some(Duration::from_millis(50))));
+
+            let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n";
+            let (status, solved) = request(port, "POST", "/solve/2023/8", input);
+            assert_eq!(status, 200);
+            assert_eq!(solved["part_one"]["timed_out"], 0.05);
+
+            server.unblock();
+        });
+    }
+}
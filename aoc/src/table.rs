@@ -0,0 +1,61 @@
+/// Rows of cells that print either as aligned plain text or as CSV.
+pub struct Table {
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn to_text(&self) -> String {
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|column| {
+                self.rows
+                    .iter()
+                    .map(|row| row[column].chars().count())
+                    .chain([self.headers[column].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let line = |cells: Vec<&str>| -> String {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect();
+            padded.join("  ").trim_end().to_string() + "\n"
+        };
+
+        let mut text = line(self.headers.clone());
+        let rules: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+        text += &line(rules.iter().map(String::as_str).collect());
+        for row in &self.rows {
+            text += &line(row.iter().map(String::as_str).collect());
+        }
+
+        text
+    }
+
+    pub fn to_csv(&self) -> String {
+        let line = |cells: Vec<&str>| -> String {
+            let escaped: Vec<String> = cells
+                .iter()
+                .map(|cell| {
+                    if cell.contains([',', '"', '\n']) {
+                        format!("\"{}\"", cell.replace('"', "\"\""))
+                    } else {
+                        cell.to_string()
+                    }
+                })
+                .collect();
+            escaped.join(",") + "\n"
+        };
+
+        let mut csv = line(self.headers.clone());
+        for row in &self.rows {
+            csv += &line(row.iter().map(String::as_str).collect());
+        }
+
+        csv
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+
+use aoc_common::{answer::Answer, input, puzzle::Puzzle};
+
+use crate::{cache::Cache, inputs::Store, run};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: Answer },
+    Unknown,
+}
+
+/// The accepted answer of each part, one per line. An empty or missing line
+/// means that part has not been answered yet.
+pub fn parse_answers(contents: &str) -> [Option<Answer>; 2] {
+    let mut lines = contents.lines().map(|line| {
+        let line = line.trim();
+        (!line.is_empty()).then(|| line.parse().unwrap_or_else(|e| match e {}))
+    });
+
+    [lines.next().flatten(), lines.next().flatten()]
+}
+
+pub fn expected_answers(store: &Store, puzzle: &Puzzle) -> Result<[Option<Answer>; 2]> {
+    Ok(store
+        .answers(puzzle)?
+        .map_or([None, None], |contents| parse_answers(&contents)))
+}
+
+pub fn verdict(expected: Option<&Answer>, answer: &Answer) -> Verdict {
+    match expected {
+        Some(expected) if expected == answer => Verdict::Correct,
+        Some(expected) => Verdict::Wrong {
+            expected: expected.clone(),
+        },
+        None => Verdict::Unknown,
+    }
+}
+
+/// Solves each puzzle that has an input and compares the answers with the
+/// accepted ones, failing if any is wrong.
+pub fn run(
+    puzzles: &[&'static Puzzle],
+    store: &Store,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let (mut wrong, mut timed_out) = (0, 0);
+
+    for puzzle in puzzles {
+        let Some(input) = store.input(puzzle)? else {
+            println!("{} {:>2}  no input", puzzle.year, puzzle.day);
+            continue;
+        };
+        let lines = input::lines(&input, puzzle.input);
+        let expected = expected_answers(store, puzzle)?;
+
+        println!("{} {:>2}  {}", puzzle.year, puzzle.day, puzzle.title);
+
+        for part in 1..=2 {
+            let solved = match run::solve(puzzle, part, &input, &lines, cache, timeout) {
+                Err(e) if e.is::<run::TimedOut>() => {
+                    timed_out += 1;
+                    println!("         Part {part}: {}", e.root_cause());
+                    continue;
+                }
+                result => result?.solved,
+            };
+            let elapsed = Duration::from_secs_f64(solved.seconds);
+
+            let verdict = match verdict(expected[part as usize - 1].as_ref(), &solved.answer) {
+                Verdict::Correct => "correct".to_string(),
+                Verdict::Wrong { expected } => {
+                    wrong += 1;
+                    format!("wrong, expected {expected}")
+                }
+                Verdict::Unknown => "no accepted answer".to_string(),
+            };
+            println!(
+                "         Part {part}: {} ({elapsed:.2?}) {verdict}",
+                solved.answer
+            );
+        }
+    }
+
+    if wrong > 0 || timed_out > 0 {
+        bail!("{wrong} answers are wrong and {timed_out} parts timed out");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::inputs::{Key, INPUTS_DIR, KEY_FILE, KEY_VARIABLE, SEALED_DIR};
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(parse_answers(""), [None, None]);
+        assert_eq!(parse_answers("288\n"), [Some(Answer::from(288_u32)), None]);
+        assert_eq!(
+            parse_answers("\n71503\n"),
+            [None, Some(Answer::from(71503_u32))]
+        );
+    }
+
+    // The fixture holds the examples of days 6 and 9 with their answers,
+    // sealed with this key, which is only ever used by the tests.
+    const FIXTURE_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_sealed_fixture_is_answered_correctly() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let store = Store {
+            plain: fixtures.join("missing"),
+            sealed: fixtures.join(SEALED_DIR),
+            key: Some(Key::from_hex(FIXTURE_KEY).unwrap()),
+            answers_file: None,
+        };
+        let puzzles = [
+            aoc_registry::find(2023, 6).unwrap(),
+            aoc_registry::find(2023, 9).unwrap(),
+        ];
+
+        for puzzle in puzzles {
+            assert!(store.input(puzzle).unwrap().is_some());
+            assert_eq!(
+                expected_answers(&store, puzzle)
+                    .unwrap()
+                    .map(|a| a.is_some()),
+                [true, true]
+            );
+        }
+        run(&puzzles, &store, None, None).unwrap();
+    }
+
+    // Runs over the sealed inputs committed to the repository, which can only
+    // be checked by someone holding the key, and is skipped without it.
+    #[test]
+    fn test_sealed_inputs_are_answered_correctly() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let Some(key) = Key::load(&root.join(KEY_FILE)).unwrap() else {
+            eprintln!("Skipped, there is no inputs key in {KEY_FILE} or {KEY_VARIABLE}");
+            return;
+        };
+        let store = Store {
+            plain: root.join(INPUTS_DIR),
+            sealed: root.join(SEALED_DIR),
+            key: Some(key),
+            answers_file: None,
+        };
+
+        let puzzles: Vec<&Puzzle> = aoc_registry::years().into_values().flatten().collect();
+        run(&puzzles, &store, None, None).unwrap();
+    }
+
+    #[test]
+    fn test_verdict() {
+        let answer = Answer::from(6440_u64);
+
+        assert_eq!(
+            verdict(Some(&"6440".parse().unwrap()), &answer),
+            Verdict::Correct
+        );
+        assert_eq!(
+            verdict(Some(&Answer::from(1_u64)), &answer),
+            Verdict::Wrong {
+                expected: Answer::from(1_u64)
+            }
+        );
+        assert_eq!(verdict(None, &answer), Verdict::Unknown);
+    }
+}
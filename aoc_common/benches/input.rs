@@ -0,0 +1,21 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use aoc_common::input::{self, Normalization};
+
+fn bench_lines(c: &mut Criterion) {
+    let input = "  Game 12: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green  \n".repeat(20_000);
+
+    let mut group = c.benchmark_group("lines");
+    for (name, normalization) in [
+        ("preserve", Normalization::PRESERVE),
+        ("trim", Normalization::TRIM),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| input::lines(black_box(&input), normalization))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_lines);
+criterion_main!(benches);
@@ -0,0 +1,167 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use num_bigint::{BigInt, BigUint};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Puzzles are answered by typing the answer in, so an answer is equal to
+// another, and serialized, exactly as it is displayed. `Unsigned(7)`,
+// `Signed(7)` and `Text("7")` are all the same answer.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(BigInt),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Big(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for Answer {}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let answer = if let Ok(n) = s.parse::<u64>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Answer::Signed(n)
+        } else if let Ok(n) = s.parse::<BigInt>() {
+            Answer::Big(n)
+        } else {
+            Answer::Text(s.to_string())
+        };
+
+        Ok(answer)
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().unwrap_or_else(|e| match e {}))
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident($target:ty): $($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(n: $source) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Unsigned(u64): u8, u16, u32, u64, usize);
+answer_from!(Signed(i64): i8, i16, i32, i64, isize);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        u64::try_from(n).map_or_else(|_| Answer::Big(n.into()), Answer::Unsigned)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        i64::try_from(n).map_or_else(|_| Answer::Big(n.into()), Answer::Signed)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        Answer::Big(n.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(142_u32).to_string(), "142");
+        assert_eq!(Answer::from(-3_i64).to_string(), "-3");
+        assert_eq!(
+            Answer::from(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(Answer::from("EFGHIJ").to_string(), "EFGHIJ");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert!(matches!("71503".parse(), Ok(Answer::Unsigned(71503))));
+        assert!(matches!("-2".parse(), Ok(Answer::Signed(-2))));
+        assert!(matches!("99999999999999999999".parse(), Ok(Answer::Big(_))));
+        assert!(matches!("LRLL".parse(), Ok(Answer::Text(_))));
+    }
+
+    #[test]
+    fn test_equality_across_variants() {
+        assert_eq!(Answer::from(7_u32), Answer::from(7_i64));
+        assert_eq!(Answer::from(7_usize), Answer::from("7"));
+        assert_eq!(Answer::from(5_u128), Answer::Unsigned(5));
+        assert_ne!(Answer::from(7_u32), Answer::from(-7_i32));
+    }
+
+    #[test]
+    fn test_serde() {
+        let answers = vec![
+            Answer::from(6440_u64),
+            Answer::from(-1_i64),
+            Answer::from(i128::MIN),
+            Answer::from("text"),
+        ];
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r#"["6440","-1","-170141183460469231731687303715884105728","text"]"#
+        );
+
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, answers);
+    }
+}
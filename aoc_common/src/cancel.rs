@@ -0,0 +1,103 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::progress::{Progress, ProgressHook};
+
+/// Lets a runner stop a solution that is taking too long. Solutions call
+/// [`CancelToken::check`] inside loops that can run for a long time and give
+/// up with [`Cancelled`] once the token has been cancelled.
+///
+/// Loops whose progress is worth watching count their steps with
+/// [`CancelToken::progress`] instead, which checks the token on every step
+/// and passes the count on to the runner's [`ProgressHook`], if it has one.
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    hook: Option<Arc<dyn ProgressHook>>,
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn with_progress(self, hook: Arc<dyn ProgressHook>) -> CancelToken {
+        CancelToken {
+            hook: Some(hook),
+            ..self
+        }
+    }
+
+    pub(crate) fn hook(&self) -> Option<&dyn ProgressHook> {
+        self.hook.as_deref()
+    }
+
+    pub fn progress(&self, label: &str, total: Option<u64>) -> Progress<'_> {
+        Progress::new(self, label, total)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// The flag behind the token, for handing to a plugin across the C ABI.
+    pub fn flag(&self) -> &AtomicBool {
+        &self.cancelled
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CancelToken")
+            .field("cancelled", &self.is_cancelled())
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cancelled before finishing")
+    }
+}
+
+impl Error for Cancelled {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_cancellation() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+
+        assert_eq!(clone.check(), Ok(()));
+        token.cancel();
+        assert!(clone.is_cancelled());
+
+        let error = anyhow::Error::from(clone.check().unwrap_err());
+        assert!(error.is::<Cancelled>());
+    }
+}
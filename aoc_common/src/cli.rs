@@ -0,0 +1,253 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Read},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use serde_json::{json, Map, Value};
+use tracing::{info, info_span};
+
+use crate::{
+    answer::Answer,
+    input::{self, Normalization},
+    logging,
+    puzzle::Puzzle,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Parser, Debug)]
+pub struct Cli {
+    /// Puzzle input file, or `-` to read stdin
+    #[arg(value_name = "INPUT", conflicts_with_all = ["input", "example"])]
+    path: Option<PathBuf>,
+
+    /// Puzzle input file, or `-` to read stdin
+    #[arg(short, long, value_name = "FILE", conflicts_with = "example")]
+    input: Option<PathBuf>,
+
+    /// Solve the worked example from the puzzle text instead of an input
+    #[arg(short, long, value_name = "N", num_args = 0..=1, default_missing_value = "1")]
+    example: Option<usize>,
+
+    /// Only solve the given part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// How answers are printed
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Report how long parsing and each part took
+    #[arg(short, long)]
+    time: bool,
+
+    /// Print more about what is going on, repeat for even more
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+
+    #[arg(skip)]
+    normalization: Normalization,
+}
+
+impl Cli {
+    pub fn from_env(puzzle: &Puzzle) -> Cli {
+        let matches = Cli::command().about(puzzle.name()).get_matches();
+
+        let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        cli.normalization = puzzle.input;
+        logging::init(cli.verbose);
+
+        cli
+    }
+
+    pub fn verbosity(&self) -> u8 {
+        self.verbose
+    }
+
+    /// The whole input, read once, for [`Cli::lines`] to borrow from.
+    pub fn input(&self, examples: &[&str]) -> Result<String> {
+        let (source, input) = match (self.example, self.path.as_ref().or(self.input.as_ref())) {
+            (Some(n), _) => {
+                let example = n
+                    .checked_sub(1)
+                    .and_then(|i| examples.get(i))
+                    .ok_or(anyhow!("There are {} examples, not {n}", examples.len()))?;
+
+                (format!("example {n}"), example.to_string())
+            }
+            (None, Some(path)) if path.as_os_str() != "-" => {
+                let input = fs::read_to_string(path)
+                    .with_context(|| format!("Could not read {}", path.display()))?;
+
+                (path.display().to_string(), input)
+            }
+            (None, path) => {
+                if path.is_none() && io::stdin().is_terminal() {
+                    return Err(anyhow!(
+                        "An input file, `-` for stdin or --example must be passed."
+                    ));
+                }
+
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+
+                ("stdin".to_string(), input)
+            }
+        };
+
+        info!(bytes = input.len(), %source, "read input");
+
+        Ok(input)
+    }
+
+    pub fn lines<'a>(&self, input: &'a str) -> Vec<&'a str> {
+        let lines = input::lines(input, self.normalization);
+        info!(lines = lines.len(), "normalized input");
+
+        lines
+    }
+
+    pub fn report(&self) -> Report {
+        Report {
+            part: self.part,
+            format: self.format,
+            time: self.time,
+            json: Map::new(),
+        }
+    }
+}
+
+pub struct Report {
+    part: Option<u8>,
+    format: Format,
+    time: bool,
+    json: Map<String, Value>,
+}
+
+impl Report {
+    pub fn parse<T>(&mut self, parse: impl FnOnce() -> Result<T>) -> Result<T> {
+        let _span = info_span!("parse").entered();
+
+        let start = Instant::now();
+        let parsed = parse()?;
+        let elapsed = start.elapsed();
+        info!(?elapsed, "parsed");
+
+        if self.time {
+            match self.format {
+                Format::Text => println!("Parse: {elapsed:.2?}"),
+                Format::Json => self.record_time("parse", elapsed),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn part_one<T: Into<Answer>>(&mut self, solve: impl FnOnce() -> Result<T>) -> Result<()> {
+        self.solve(1, solve)
+    }
+
+    pub fn part_two<T: Into<Answer>>(&mut self, solve: impl FnOnce() -> Result<T>) -> Result<()> {
+        self.solve(2, solve)
+    }
+
+    pub fn finish(self) -> Result<()> {
+        if self.format == Format::Json {
+            println!("{}", serde_json::to_string_pretty(&self.json)?);
+        }
+
+        Ok(())
+    }
+
+    fn solve<T: Into<Answer>>(
+        &mut self,
+        part: u8,
+        solve: impl FnOnce() -> Result<T>,
+    ) -> Result<()> {
+        if self.part.is_some_and(|selected| selected != part) {
+            return Ok(());
+        }
+
+        let _span = info_span!("solve", part).entered();
+
+        let start = Instant::now();
+        let answer: Answer = solve()?.into();
+        let elapsed = start.elapsed();
+        info!(%answer, ?elapsed, "solved");
+
+        let key = if part == 1 { "part_one" } else { "part_two" };
+
+        match self.format {
+            Format::Text if self.time => println!("Part {part}: {answer} ({elapsed:.2?})"),
+            Format::Text => println!("Part {part}: {answer}"),
+            Format::Json => {
+                self.json.insert(key.to_string(), json!(answer));
+                if self.time {
+                    self.record_time(key, elapsed);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn record_time(&mut self, key: &str, elapsed: Duration) {
+        let timings = self
+            .json
+            .entry("seconds")
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        if let Value::Object(timings) = timings {
+            timings.insert(key.to_string(), json!(elapsed.as_secs_f64()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [&str; 2] = ["1 2\n3 4", "5 6"];
+
+    #[test]
+    fn test_example_lines() {
+        let cli = Cli::try_parse_from(["day", "--example"]).unwrap();
+        let input = cli.input(&EXAMPLES).unwrap();
+        assert_eq!(cli.lines(&input), vec!["1 2", "3 4"]);
+
+        let cli = Cli::try_parse_from(["day", "-e", "2"]).unwrap();
+        let input = cli.input(&EXAMPLES).unwrap();
+        assert_eq!(cli.lines(&input), vec!["5 6"]);
+
+        let cli = Cli::try_parse_from(["day", "-e", "3"]).unwrap();
+        assert!(cli.input(&EXAMPLES).is_err());
+    }
+
+    #[test]
+    fn test_argument_conflicts() {
+        assert!(Cli::try_parse_from(["day", "input.txt"]).is_ok());
+        assert!(Cli::try_parse_from(["day", "--input", "input.txt", "-p", "2"]).is_ok());
+        assert!(Cli::try_parse_from(["day", "input.txt", "--example"]).is_err());
+        assert!(Cli::try_parse_from(["day", "input.txt", "--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_part_selection() {
+        let cli = Cli::try_parse_from(["day", "-e", "-p", "2", "-f", "json"]).unwrap();
+        let mut report = cli.report();
+
+        report.part_one(|| Ok(1)).unwrap();
+        report.part_two(|| Ok(2)).unwrap();
+
+        assert_eq!(report.json.get("part_one"), None);
+        assert_eq!(report.json.get("part_two"), Some(&json!("2")));
+    }
+}
@@ -0,0 +1,128 @@
+/// Small xorshift generator so that scaling runs are reproducible without
+/// pulling in a random number crate.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n.max(1)
+    }
+
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Writes inputs of any size, to time how a solution scales.
+pub struct Generator {
+    /// What a unit of `size` means for this day's input.
+    pub unit: &'static str,
+    pub base_size: usize,
+    pub generate: fn(usize, &mut Rng) -> String,
+}
+
+/// Rewrites a real input into a look-alike that can be shared.
+pub struct Anonymizer {
+    /// Parts whose answer cannot change, because the rewrite keeps everything
+    /// they depend on.
+    pub preserves: [bool; 2],
+    pub anonymize: fn(&str, &mut Rng) -> String,
+}
+
+pub fn map_lines(input: &str, mut map: impl FnMut(&str) -> String) -> String {
+    input.lines().map(|line| map(line) + "\n").collect()
+}
+
+/// Replaces every run of digits in `text`. Anything else is copied as it is,
+/// so inputs the solvers reject stay just as broken.
+pub fn map_numbers(text: &str, mut map: impl FnMut(&str) -> String) -> String {
+    let mut mapped = String::new();
+    let mut digits = String::new();
+
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        if !digits.is_empty() {
+            mapped += &map(&digits);
+            digits.clear();
+        }
+        mapped.push(c);
+    }
+    if !digits.is_empty() {
+        mapped += &map(&digits);
+    }
+
+    mapped
+}
+
+/// A random number written with as many digits as `digits`, so that columns
+/// stay aligned.
+pub fn same_length_number(digits: &str, rng: &mut Rng) -> String {
+    (0..digits.len())
+        .map(|i| {
+            let lowest = if i == 0 && digits.len() > 1 { 1 } else { 0 };
+            char::from(b'0' + rng.range(lowest, 9) as u8)
+        })
+        .collect()
+}
+
+/// The non-blank lines of `input`, each mapped, in a random order.
+pub fn shuffled_lines(
+    input: &str,
+    rng: &mut Rng,
+    mut map: impl FnMut(&str, &mut Rng) -> String,
+) -> String {
+    let mut lines: Vec<String> = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| map(line, rng))
+        .collect();
+    rng.shuffle(&mut lines);
+
+    lines.iter().map(|line| line.clone() + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_numbers_keeps_everything_else() {
+        let mapped = map_numbers("a12 b3-", |digits| digits.len().to_string());
+
+        assert_eq!(mapped, "a2 b1-");
+    }
+
+    #[test]
+    fn test_same_length_number() {
+        let mut rng = Rng::new(9);
+
+        for digits in ["0", "7", "42", "1234"] {
+            let number = same_length_number(digits, &mut rng);
+            assert_eq!(number.len(), digits.len());
+            assert!(number.len() == 1 || !number.starts_with('0'));
+        }
+    }
+}
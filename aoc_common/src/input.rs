@@ -0,0 +1,204 @@
+use std::fmt;
+
+use tracing::warn;
+
+/// How a day wants its input lines prepared. Whitespace inside a line is
+/// always kept; leading and trailing whitespace only goes if the day trims.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Normalization {
+    pub trim_lines: bool,
+    pub drop_trailing_blank_lines: bool,
+}
+
+impl Normalization {
+    /// Keeps every line exactly as written, which grids need.
+    pub const PRESERVE: Normalization = Normalization {
+        trim_lines: false,
+        drop_trailing_blank_lines: true,
+    };
+
+    /// Trims each line, for inputs that are only whitespace separated tokens.
+    pub const TRIM: Normalization = Normalization {
+        trim_lines: true,
+        drop_trailing_blank_lines: true,
+    };
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization::PRESERVE
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Irregularity {
+    ByteOrderMark,
+    CarriageReturns(usize),
+    Tabs(usize),
+    LeadingWhitespace(usize),
+    TrailingWhitespace(usize),
+    TrailingBlankLines(usize),
+}
+
+impl fmt::Display for Irregularity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Irregularity::ByteOrderMark => write!(f, "input starts with a byte order mark"),
+            Irregularity::CarriageReturns(n) => write!(f, "{n} lines end with CRLF"),
+            Irregularity::Tabs(n) => write!(f, "{n} lines contain tabs"),
+            Irregularity::LeadingWhitespace(n) => write!(f, "{n} lines start with whitespace"),
+            Irregularity::TrailingWhitespace(n) => write!(f, "{n} lines end with whitespace"),
+            Irregularity::TrailingBlankLines(n) => write!(f, "{n} trailing blank lines"),
+        }
+    }
+}
+
+/// The lines borrow from the input, so a day parses one buffer throughout.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Normalized<'a> {
+    pub lines: Vec<&'a str>,
+    pub irregularities: Vec<Irregularity>,
+}
+
+pub fn normalize(input: &str, normalization: Normalization) -> Normalized<'_> {
+    let mut irregularities = vec![];
+
+    let input = match input.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            irregularities.push(Irregularity::ByteOrderMark);
+            rest
+        }
+        None => input,
+    };
+
+    let carriage_returns = input.matches("\r\n").count();
+    if carriage_returns > 0 {
+        irregularities.push(Irregularity::CarriageReturns(carriage_returns));
+    }
+
+    let tabs = input.lines().filter(|line| line.contains('\t')).count();
+    if tabs > 0 {
+        irregularities.push(Irregularity::Tabs(tabs));
+    }
+
+    // Lines are trimmed on request, so whitespace around them is only worth a
+    // warning when it is kept.
+    if !normalization.trim_lines {
+        let count = |padded: fn(&str) -> bool| {
+            input
+                .lines()
+                .filter(|line| !line.trim().is_empty() && padded(line))
+                .count()
+        };
+
+        let leading_whitespace = count(|line| line.starts_with(char::is_whitespace));
+        if leading_whitespace > 0 {
+            irregularities.push(Irregularity::LeadingWhitespace(leading_whitespace));
+        }
+
+        let trailing_whitespace = count(|line| line.ends_with(char::is_whitespace));
+        if trailing_whitespace > 0 {
+            irregularities.push(Irregularity::TrailingWhitespace(trailing_whitespace));
+        }
+    }
+
+    let mut lines: Vec<&str> = input
+        .lines()
+        .map(|line| {
+            if normalization.trim_lines {
+                line.trim()
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    let blank_lines = lines
+        .iter()
+        .rev()
+        .take_while(|line| line.trim().is_empty())
+        .count();
+    if blank_lines > 0 {
+        irregularities.push(Irregularity::TrailingBlankLines(blank_lines));
+        if normalization.drop_trailing_blank_lines {
+            lines.truncate(lines.len() - blank_lines);
+        }
+    }
+
+    Normalized {
+        lines,
+        irregularities,
+    }
+}
+
+/// Normalizes `input`, logging a warning for each irregularity found in it.
+pub fn lines(input: &str, normalization: Normalization) -> Vec<&str> {
+    let normalized = normalize(input, normalization);
+
+    for irregularity in &normalized.irregularities {
+        warn!(%irregularity, "irregular input");
+    }
+
+    normalized.lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_input() {
+        let normalized = normalize("467..114..\n...*......\n", Normalization::PRESERVE);
+
+        assert_eq!(normalized.lines, vec!["467..114..", "...*......"]);
+        assert!(normalized.irregularities.is_empty());
+    }
+
+    #[test]
+    fn test_bom_and_crlf() {
+        let normalized = normalize("\u{feff}a b\r\nc d\r\n", Normalization::PRESERVE);
+
+        assert_eq!(normalized.lines, vec!["a b", "c d"]);
+        assert_eq!(
+            normalized.irregularities,
+            vec![
+                Irregularity::ByteOrderMark,
+                Irregularity::CarriageReturns(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_whitespace_is_kept_unless_trimmed() {
+        let input = "  .#. \n\t#.#\n\n\n";
+
+        let preserved = normalize(input, Normalization::PRESERVE);
+        assert_eq!(preserved.lines, vec!["  .#. ", "\t#.#"]);
+        assert_eq!(
+            preserved.irregularities,
+            vec![
+                Irregularity::Tabs(1),
+                Irregularity::LeadingWhitespace(2),
+                Irregularity::TrailingWhitespace(1),
+                Irregularity::TrailingBlankLines(2)
+            ]
+        );
+
+        let trimmed = normalize(input, Normalization::TRIM);
+        assert_eq!(trimmed.lines, vec![".#.", "#.#"]);
+        assert_eq!(
+            trimmed.irregularities,
+            vec![Irregularity::Tabs(1), Irregularity::TrailingBlankLines(2)]
+        );
+    }
+
+    #[test]
+    fn test_trailing_blank_lines_can_be_kept() {
+        let normalization = Normalization {
+            drop_trailing_blank_lines: false,
+            ..Normalization::PRESERVE
+        };
+
+        assert_eq!(normalize("a\n\n", normalization).lines, vec!["a", ""]);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod answer;
+pub mod cancel;
+pub mod cli;
+pub mod generate;
+pub mod input;
+pub mod logging;
+pub mod plugin;
+pub mod progress;
+pub mod puzzle;
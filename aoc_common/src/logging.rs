@@ -0,0 +1,39 @@
+use std::io;
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{fmt::MakeWriter, EnvFilter};
+
+// `RUST_LOG` takes precedence so single modules can be turned up, otherwise
+// each `-v` enables one more level. Without either nothing is logged.
+fn filter(verbosity: u8) -> EnvFilter {
+    let level = match verbosity {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+
+    EnvFilter::builder()
+        .with_default_directive(level.into())
+        .from_env_lossy()
+}
+
+pub fn init(verbosity: u8) {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter(verbosity))
+        .with_writer(io::stderr)
+        .try_init();
+}
+
+/// Logs to `writer` instead of stderr, without colours, for a program that
+/// shows its own logs such as a terminal UI.
+pub fn init_with_writer<W>(verbosity: u8, writer: W)
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter(verbosity))
+        .with_writer(writer)
+        .with_ansi(false)
+        .try_init();
+}
@@ -0,0 +1,227 @@
+use std::{
+    ffi::{c_char, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice, str,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{cancel::CancelToken, input, puzzle::Puzzle};
+
+/// Bumped whenever [`Plugin`] or [`Outcome`] change shape.
+pub const ABI_VERSION: u32 = 1;
+
+pub const VERSION_SYMBOL: &str = "aoc_plugin_abi_version";
+pub const ENTRY_SYMBOL: &str = "aoc_plugin_v1";
+
+/// Signature of the function behind [`VERSION_SYMBOL`].
+pub type VersionFn = unsafe extern "C" fn() -> u32;
+/// Signature of the function behind [`ENTRY_SYMBOL`].
+pub type EntryFn = unsafe extern "C" fn() -> *const Plugin;
+
+/// A day as a plugin describes it, for a runner that was not built with it.
+///
+/// The library exports a function named [`VERSION_SYMBOL`] returning the
+/// [`ABI_VERSION`] it was built for, which is all a runner may rely on before
+/// checking that version, and one named [`ENTRY_SYMBOL`] returning this. Day
+/// crates export both with [`export_plugin!`](crate::export_plugin) behind
+/// their `plugin` feature. Every pointer stays valid for as long as the
+/// library is loaded.
+#[repr(C)]
+pub struct Plugin {
+    pub year: u32,
+    pub day: u32,
+    /// NUL-terminated UTF-8.
+    pub title: *const c_char,
+    /// `example_count` NUL-terminated UTF-8 strings.
+    pub examples: *const *const c_char,
+    pub example_count: usize,
+    pub trim_lines: bool,
+    pub drop_trailing_blank_lines: bool,
+    /// Solves `part` of the UTF-8 `input`, giving up once `cancelled` is set,
+    /// and fills in `outcome`. Safe to call from several threads at once.
+    pub solve: unsafe extern "C" fn(
+        part: u8,
+        input: *const u8,
+        input_len: usize,
+        cancelled: *const AtomicBool,
+        outcome: *mut Outcome,
+    ),
+    /// Frees the text of an [`Outcome`].
+    pub free: unsafe extern "C" fn(text: *mut c_char),
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Solved = 0,
+    Failed = 1,
+    Cancelled = 2,
+}
+
+/// A status as it crosses the ABI, which is only ever a number. Anything the
+/// enum has no variant for comes back as the error.
+impl TryFrom<u32> for Status {
+    type Error = u32;
+
+    fn try_from(status: u32) -> Result<Status, u32> {
+        match status {
+            0 => Ok(Status::Solved),
+            1 => Ok(Status::Failed),
+            2 => Ok(Status::Cancelled),
+            unknown => Err(unknown),
+        }
+    }
+}
+
+/// The answer when solved, otherwise what went wrong. The text belongs to the
+/// plugin and goes back to it through [`Plugin::free`].
+#[repr(C)]
+pub struct Outcome {
+    /// A [`Status`], read with `Status::try_from`.
+    pub status: u32,
+    pub text: *mut c_char,
+}
+
+// The pointers only ever refer to data leaked for the life of the library.
+struct Exported {
+    plugin: Plugin,
+    _strings: Vec<CString>,
+    _examples: Vec<*const c_char>,
+}
+
+unsafe impl Send for Exported {}
+unsafe impl Sync for Exported {}
+
+static EXPORTED: OnceLock<(&'static Puzzle, Exported)> = OnceLock::new();
+
+/// The [`Plugin`] for `puzzle`, built on first use. A library exports a
+/// single puzzle, so later calls return the first one.
+pub fn export(puzzle: &'static Puzzle) -> *const Plugin {
+    let (_, exported) = EXPORTED.get_or_init(|| {
+        let c_string = |s: &str| CString::new(s.replace('\0', "")).unwrap_or_default();
+        let mut strings = vec![c_string(puzzle.title)];
+        strings.extend(puzzle.examples.iter().map(|example| c_string(example)));
+        let examples: Vec<_> = strings[1..].iter().map(|s| s.as_ptr()).collect();
+
+        let plugin = Plugin {
+            year: puzzle.year,
+            day: puzzle.day,
+            title: strings[0].as_ptr(),
+            examples: examples.as_ptr(),
+            example_count: examples.len(),
+            trim_lines: puzzle.input.trim_lines,
+            drop_trailing_blank_lines: puzzle.input.drop_trailing_blank_lines,
+            solve: exported_solve,
+            free: exported_free,
+        };
+
+        (
+            puzzle,
+            Exported {
+                plugin,
+                _strings: strings,
+                _examples: examples,
+            },
+        )
+    });
+
+    &exported.plugin
+}
+
+fn outcome(status: Status, text: String) -> Outcome {
+    Outcome {
+        status: status as u32,
+        text: CString::new(text.replace('\0', ""))
+            .unwrap_or_default()
+            .into_raw(),
+    }
+}
+
+// How often a plugin looks at the runner's flag, well within the grace a
+// runner gives a cancelled part.
+const CANCEL_POLL: Duration = Duration::from_millis(20);
+
+/// Solves with a token of the plugin's own, cancelled once the runner sets
+/// its flag. The flag only lives for the call while solutions may keep clones
+/// of their token, so the token cannot share it.
+fn solve(puzzle: &Puzzle, part: u8, input: &str, cancelled: &AtomicBool) -> Outcome {
+    let lines = input::normalize(input, puzzle.input).lines;
+    let cancel = CancelToken::new();
+    let done = AtomicBool::new(false);
+
+    let solved = thread::scope(|scope| {
+        let watcher = scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                if cancelled.load(Ordering::Relaxed) {
+                    cancel.cancel();
+                    break;
+                }
+                thread::park_timeout(CANCEL_POLL);
+            }
+        });
+
+        let solved = panic::catch_unwind(AssertUnwindSafe(|| match part {
+            1 => puzzle.solution.part_one(&lines, &cancel),
+            _ => puzzle.solution.part_two(&lines, &cancel),
+        }));
+        done.store(true, Ordering::Relaxed);
+        watcher.thread().unpark();
+
+        solved
+    });
+
+    match solved {
+        Ok(Ok(answer)) => outcome(Status::Solved, answer.to_string()),
+        Ok(Err(_)) if cancel.is_cancelled() => outcome(Status::Cancelled, String::new()),
+        Ok(Err(e)) => outcome(Status::Failed, format!("{e:#}")),
+        Err(_) => outcome(Status::Failed, "panicked".to_string()),
+    }
+}
+
+unsafe extern "C" fn exported_solve(
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    cancelled: *const AtomicBool,
+    out: *mut Outcome,
+) {
+    let result = match (
+        EXPORTED.get(),
+        str::from_utf8(slice::from_raw_parts(input, input_len)),
+    ) {
+        (None, _) => outcome(Status::Failed, format!("{ENTRY_SYMBOL} was not called")),
+        (_, Err(e)) => outcome(Status::Failed, format!("The input is not UTF-8: {e}")),
+        (Some((puzzle, _)), Ok(input)) => solve(puzzle, part, input, &*cancelled),
+    };
+
+    ptr::write(out, result);
+}
+
+unsafe extern "C" fn exported_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+/// Exports `$puzzle` as this library's plugin. Only use it behind the day
+/// crate's `plugin` feature, as the exported names clash once two days are
+/// linked into the same binary.
+#[macro_export]
+macro_rules! export_plugin {
+    ($puzzle:expr) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_abi_version() -> u32 {
+            $crate::plugin::ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_v1() -> *const $crate::plugin::Plugin {
+            $crate::plugin::export(&$puzzle)
+        }
+    };
+}
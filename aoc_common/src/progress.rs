@@ -0,0 +1,129 @@
+use crate::cancel::{CancelToken, Cancelled};
+
+/// Receives progress from long loops inside a solution, e.g. to draw a
+/// progress bar. Calls come from the thread solving the part.
+pub trait ProgressHook: Send + Sync {
+    /// A loop of `total` steps, or of an unknown number of them, begins.
+    fn start(&self, label: &str, total: Option<u64>);
+    fn update(&self, done: u64);
+    fn finish(&self);
+}
+
+// Hooks are told about every this many steps of a loop of unknown length,
+// and about every thousandth of a known one.
+const UNKNOWN_TOTAL_STEP: u64 = 1 << 12;
+
+/// Counts the steps of one loop, checking for cancellation at each of them.
+pub struct Progress<'a> {
+    token: &'a CancelToken,
+    done: u64,
+    reported: u64,
+    step: u64,
+}
+
+impl<'a> Progress<'a> {
+    pub(crate) fn new(token: &'a CancelToken, label: &str, total: Option<u64>) -> Progress<'a> {
+        if let Some(hook) = token.hook() {
+            hook.start(label, total);
+        }
+
+        Progress {
+            token,
+            done: 0,
+            reported: 0,
+            step: total.map_or(UNKNOWN_TOTAL_STEP, |total| (total / 1000).max(1)),
+        }
+    }
+
+    pub fn tick(&mut self) -> Result<(), Cancelled> {
+        self.advance(1)
+    }
+
+    pub fn advance(&mut self, steps: u64) -> Result<(), Cancelled> {
+        self.token.check()?;
+        self.done += steps;
+
+        if self.done - self.reported >= self.step {
+            if let Some(hook) = self.token.hook() {
+                hook.update(self.done);
+            }
+            self.reported = self.done;
+        }
+
+        Ok(())
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+}
+
+impl Drop for Progress<'_> {
+    fn drop(&mut self) {
+        if let Some(hook) = self.token.hook() {
+            hook.update(self.done);
+            hook.finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl ProgressHook for Recorder {
+        fn start(&self, label: &str, total: Option<u64>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("start {label} {total:?}"));
+        }
+
+        fn update(&self, done: u64) {
+            self.0.lock().unwrap().push(format!("update {done}"));
+        }
+
+        fn finish(&self) {
+            self.0.lock().unwrap().push("finish".to_string());
+        }
+    }
+
+    #[test]
+    fn test_updates_are_batched() {
+        let recorder = Arc::new(Recorder::default());
+        let token = CancelToken::new().with_progress(recorder.clone());
+
+        let mut progress = token.progress("seeds", Some(3000));
+        for _ in 0..7 {
+            progress.tick().unwrap();
+        }
+        drop(progress);
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                "start seeds Some(3000)",
+                "update 3",
+                "update 6",
+                "update 7",
+                "finish"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ticks_stop_once_cancelled() {
+        let token = CancelToken::new();
+        let mut progress = token.progress("steps", None);
+
+        progress.tick().unwrap();
+        token.cancel();
+        assert_eq!(progress.tick(), Err(Cancelled));
+        assert_eq!(progress.done(), 1);
+    }
+}
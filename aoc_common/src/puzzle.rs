@@ -0,0 +1,87 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::{
+    answer::Answer,
+    cancel::CancelToken,
+    generate::{Anonymizer, Generator},
+    input::Normalization,
+};
+
+pub trait Solution: Sync {
+    /// Solutions with loops that can run for a long time check `cancel` in
+    /// them, so that a runner can give up on the part.
+    fn part_one(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer>;
+    fn part_two(&self, lines: &[&str], cancel: &CancelToken) -> Result<Answer>;
+
+    /// The parsed form of the input, to check what the parser produced.
+    fn dump(&self, _lines: &[&str]) -> Result<Value> {
+        Err(anyhow!("This puzzle has no parsed form to dump"))
+    }
+
+    /// How a part's answer was reached, step by step, for the `item`th item
+    /// of the input (counting from 1) or for all of them.
+    fn explain(&self, _lines: &[&str], _part: u8, _item: Option<usize>) -> Result<String> {
+        Err(anyhow!("This puzzle cannot explain its answers"))
+    }
+
+    /// Writes inputs of growing size for `aoc scale`.
+    fn generator(&self) -> Option<Generator> {
+        None
+    }
+
+    /// Rewrites real inputs into look-alikes for `aoc anonymize`.
+    fn anonymizer(&self) -> Option<Anonymizer> {
+        None
+    }
+}
+
+/// The items an explanation covers, numbered from 1: just the chosen one, or
+/// all of them.
+pub fn chosen<'a, T>(
+    items: &'a [T],
+    item: Option<usize>,
+    noun: &str,
+) -> Result<Vec<(usize, &'a T)>> {
+    let mut numbered = (1..).zip(items);
+
+    match item {
+        None => Ok(numbered.collect()),
+        Some(n) => numbered
+            .nth(n.wrapping_sub(1))
+            .map(|chosen| vec![chosen])
+            .ok_or_else(|| anyhow!("There are {} {noun}, not {n}", items.len())),
+    }
+}
+
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub examples: &'static [&'static str],
+    pub input: Normalization,
+    pub solution: &'static dyn Solution,
+}
+
+impl Puzzle {
+    pub fn name(&self) -> String {
+        format!("Day {}: {}", self.day, self.title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chosen_items() {
+        let items = ["a", "b", "c"];
+
+        assert_eq!(chosen(&items, None, "lines").unwrap().len(), 3);
+        assert_eq!(chosen(&items, Some(2), "lines").unwrap(), [(2, &"b")]);
+        assert_eq!(
+            chosen(&items, Some(0), "lines").unwrap_err().to_string(),
+            "There are 3 lines, not 0"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated from src/lib.rs by build.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// What became of a call. With anything but `AOC_STATUS_OK`, the string
+// handed back says what went wrong instead.
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  // No solution for that year and day.
+  AOC_STATUS_UNKNOWN_PUZZLE = 1,
+  // A part other than 1 or 2, an example that does not exist, or a null
+  // pointer.
+  AOC_STATUS_INVALID_ARGUMENT = 2,
+  // The input is not UTF-8.
+  AOC_STATUS_INVALID_INPUT = 3,
+  // The solution failed on the input.
+  AOC_STATUS_FAILED = 4,
+} AocStatus;
+
+// Solves `part` (1 or 2) of a day's puzzle for the `input_len` bytes of UTF-8
+// at `input`, which need not be NUL-terminated.
+//
+// `*out` is set to the answer, or to what went wrong when the status is not
+// `AOC_STATUS_OK`. Either way it belongs to the caller, who frees it with
+// `aoc_string_free`.
+//
+// # Safety
+//
+// `input` must point to `input_len` readable bytes, or be null when
+// `input_len` is 0, and `out` must point to writable memory for a pointer.
+enum AocStatus aoc_solve(uint32_t year,
+                         uint32_t day,
+                         uint8_t part,
+                         const char *input,
+                         size_t input_len,
+                         char **out);
+
+// Copies the `index`th example (from 0) in a day's puzzle text into `*out`,
+// or what went wrong when the status is not `AOC_STATUS_OK`. The caller
+// frees it with `aoc_string_free`.
+//
+// # Safety
+//
+// `out` must point to writable memory for a pointer.
+enum AocStatus aoc_example(uint32_t year, uint32_t day, size_t index, char **out);
+
+// Frees a string handed out by this library. Null is ignored.
+//
+// # Safety
+//
+// `text` must have come from this library and not have been freed already.
+void aoc_string_free(char *text);
+
+#endif /* AOC_H */
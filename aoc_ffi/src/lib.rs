@@ -0,0 +1,220 @@
+use std::{
+    ffi::{c_char, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice, str,
+};
+
+use aoc_common::{cancel::CancelToken, input, puzzle::Puzzle};
+
+/// What became of a call. With anything but `AOC_STATUS_OK`, the string
+/// handed back says what went wrong instead.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AocStatus {
+    Ok = 0,
+    /// No solution for that year and day.
+    UnknownPuzzle = 1,
+    /// A part other than 1 or 2, an example that does not exist, or a null
+    /// pointer.
+    InvalidArgument = 2,
+    /// The input is not UTF-8.
+    InvalidInput = 3,
+    /// The solution failed on the input.
+    Failed = 4,
+}
+
+struct Failure(AocStatus, String);
+
+fn find(year: u32, day: u32) -> Result<&'static Puzzle, Failure> {
+    aoc_registry::find(year, day).map_err(|e| Failure(AocStatus::UnknownPuzzle, e.to_string()))
+}
+
+fn solve(year: u32, day: u32, part: u8, input: &[u8]) -> Result<String, Failure> {
+    let puzzle = find(year, day)?;
+    let input = str::from_utf8(input).map_err(|e| {
+        Failure(
+            AocStatus::InvalidInput,
+            format!("The input is not UTF-8: {e}"),
+        )
+    })?;
+
+    let lines = input::lines(input, puzzle.input);
+    let cancel = CancelToken::new();
+    let answer = match part {
+        1 => puzzle.solution.part_one(&lines, &cancel),
+        2 => puzzle.solution.part_two(&lines, &cancel),
+        _ => {
+            return Err(Failure(
+                AocStatus::InvalidArgument,
+                format!("There is no part {part}, only 1 and 2"),
+            ))
+        }
+    };
+
+    answer
+        .map(|answer| answer.to_string())
+        .map_err(|e| Failure(AocStatus::Failed, format!("{e:#}")))
+}
+
+fn example(year: u32, day: u32, index: usize) -> Result<String, Failure> {
+    let puzzle = find(year, day)?;
+
+    puzzle
+        .examples
+        .get(index)
+        .map(|example| example.to_string())
+        .ok_or_else(|| {
+            Failure(
+                AocStatus::InvalidArgument,
+                format!("{} has {} examples", puzzle.name(), puzzle.examples.len()),
+            )
+        })
+}
+
+/// Runs `call` without letting a panic cross into C, and hands its text to
+/// the caller through `out`.
+unsafe fn respond(
+    out: *mut *mut c_char,
+    call: impl FnOnce() -> Result<String, Failure>,
+) -> AocStatus {
+    if out.is_null() {
+        return AocStatus::InvalidArgument;
+    }
+
+    let (status, text) = match panic::catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(text)) => (AocStatus::Ok, text),
+        Ok(Err(Failure(status, message))) => (status, message),
+        Err(_) => (AocStatus::Failed, "The solution panicked".to_string()),
+    };
+    let text = CString::new(text.replace('\0', "")).unwrap_or_default();
+
+    ptr::write(out, text.into_raw());
+    status
+}
+
+/// Solves `part` (1 or 2) of a day's puzzle for the `input_len` bytes of UTF-8
+/// at `input`, which need not be NUL-terminated.
+///
+/// `*out` is set to the answer, or to what went wrong when the status is not
+/// `AOC_STATUS_OK`. Either way it belongs to the caller, who frees it with
+/// `aoc_string_free`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, or be null when
+/// `input_len` is 0, and `out` must point to writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u8,
+    input: *const c_char,
+    input_len: usize,
+    out: *mut *mut c_char,
+) -> AocStatus {
+    respond(out, || {
+        let input = match (input.is_null(), input_len) {
+            (true, 0) => &[][..],
+            (true, _) => {
+                return Err(Failure(
+                    AocStatus::InvalidArgument,
+                    "The input is null".to_string(),
+                ))
+            }
+            (false, len) => slice::from_raw_parts(input.cast(), len),
+        };
+
+        solve(year, day, part, input)
+    })
+}
+
+/// Copies the `index`th example (from 0) in a day's puzzle text into `*out`,
+/// or what went wrong when the status is not `AOC_STATUS_OK`. The caller
+/// frees it with `aoc_string_free`.
+///
+/// # Safety
+///
+/// `out` must point to writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_example(
+    year: u32,
+    day: u32,
+    index: usize,
+    out: *mut *mut c_char,
+) -> AocStatus {
+    respond(out, || example(year, day, index))
+}
+
+/// Frees a string handed out by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `text` must have come from this library and not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    fn call(call: impl FnOnce(*mut *mut c_char) -> AocStatus) -> (AocStatus, String) {
+        let mut out = ptr::null_mut();
+        let status = call(&mut out);
+        let text = unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_string();
+        unsafe { aoc_string_free(out) };
+
+        (status, text)
+    }
+
+    #[test]
+    fn test_solves_an_example() {
+        let (status, example) = call(|out| unsafe { aoc_example(2023, 6, 0, out) });
+        assert_eq!(status, AocStatus::Ok);
+
+        let solved = call(|out| unsafe {
+            aoc_solve(2023, 6, 2, example.as_ptr().cast(), example.len(), out)
+        });
+        assert_eq!(solved, (AocStatus::Ok, "71503".to_string()));
+    }
+
+    #[test]
+    fn test_failures_come_with_a_message() {
+        let input = b"Time: 7\nDistance: 9";
+
+        let (status, message) =
+            call(|out| unsafe { aoc_solve(1999, 1, 1, input.as_ptr().cast(), input.len(), out) });
+        assert_eq!(status, AocStatus::UnknownPuzzle);
+        assert_eq!(message, "Day 1 of 1999 has not been solved yet");
+
+        let (status, _) =
+            call(|out| unsafe { aoc_solve(2023, 6, 3, input.as_ptr().cast(), input.len(), out) });
+        assert_eq!(status, AocStatus::InvalidArgument);
+
+        let (status, _) =
+            call(|out| unsafe { aoc_solve(2023, 6, 1, [0xff].as_ptr().cast(), 1, out) });
+        assert_eq!(status, AocStatus::InvalidInput);
+
+        let (status, _) = call(|out| unsafe { aoc_example(2023, 6, 99, out) });
+        assert_eq!(status, AocStatus::InvalidArgument);
+
+        assert_eq!(
+            unsafe {
+                aoc_solve(
+                    2023,
+                    6,
+                    1,
+                    input.as_ptr().cast(),
+                    input.len(),
+                    ptr::null_mut(),
+                )
+            },
+            AocStatus::InvalidArgument
+        );
+    }
+}
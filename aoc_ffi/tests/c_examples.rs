@@ -0,0 +1,32 @@
+use std::{env, path::Path, process::Command};
+
+// Builds tests/examples.c against the static library cargo has just built
+// alongside this test, and runs it.
+#[test]
+fn test_c_harness_solves_the_examples() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let exe = env::current_exe().unwrap();
+    let deps_dir = exe.parent().unwrap();
+    let harness = Path::new(env!("CARGO_TARGET_TMPDIR")).join("examples");
+
+    if Command::new("cc").arg("--version").output().is_err() {
+        eprintln!("Skipping the C harness, as there is no `cc` to build it with");
+        return;
+    }
+
+    let compiled = Command::new("cc")
+        .arg(manifest_dir.join("tests/examples.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(deps_dir.join("libaoc_ffi.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&harness)
+        .status()
+        .unwrap();
+    assert!(compiled.success(), "could not build tests/examples.c");
+
+    let output = Command::new(&harness).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.ends_with("examples, 0 failed\n"), "{stdout}");
+}
@@ -0,0 +1,70 @@
+/* Solves every example through the C interface and checks the answers. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+struct expected {
+  uint32_t year;
+  uint32_t day;
+  size_t example;
+  uint8_t part;
+  const char *answer;
+};
+
+static const struct expected EXPECTED[] = {
+    {2023, 1, 0, 1, "142"},     {2023, 1, 1, 2, "281"},
+    {2023, 2, 0, 1, "8"},       {2023, 2, 0, 2, "2286"},
+    {2023, 3, 0, 1, "4361"},    {2023, 3, 0, 2, "467835"},
+    {2023, 4, 0, 1, "13"},      {2023, 4, 0, 2, "30"},
+    {2023, 5, 0, 1, "35"},      {2023, 5, 0, 2, "46"},
+    {2023, 6, 0, 1, "288"},     {2023, 6, 0, 2, "71503"},
+    {2023, 7, 0, 1, "6440"},    {2023, 7, 0, 2, "5905"},
+    {2023, 8, 0, 1, "2"},       {2023, 8, 1, 1, "6"},
+    {2023, 8, 2, 2, "6"},       {2023, 9, 0, 1, "114"},
+    {2023, 9, 0, 2, "2"},
+};
+
+static int check(const struct expected *expected) {
+  char *example;
+  char *answer;
+  int failed = 0;
+
+  if (aoc_example(expected->year, expected->day, expected->example, &example) != AOC_STATUS_OK) {
+    printf("%u day %u: %s\n", expected->year, expected->day, example);
+    aoc_string_free(example);
+    return 1;
+  }
+
+  AocStatus status = aoc_solve(expected->year, expected->day, expected->part, example,
+                               strlen(example), &answer);
+  if (status != AOC_STATUS_OK || strcmp(answer, expected->answer) != 0) {
+    printf("%u day %u example %zu part %u: expected %s, got %s (status %d)\n", expected->year,
+           expected->day, expected->example, expected->part, expected->answer, answer, status);
+    failed = 1;
+  }
+
+  aoc_string_free(answer);
+  aoc_string_free(example);
+  return failed;
+}
+
+int main(void) {
+  size_t count = sizeof(EXPECTED) / sizeof(EXPECTED[0]);
+  int failures = 0;
+
+  for (size_t i = 0; i < count; i++) {
+    failures += check(&EXPECTED[i]);
+  }
+
+  char *message;
+  if (aoc_solve(1999, 1, 1, "", 0, &message) != AOC_STATUS_UNKNOWN_PUZZLE) {
+    printf("an unsolved day was not reported as unknown: %s\n", message);
+    failures++;
+  }
+  aoc_string_free(message);
+
+  printf("%zu examples, %d failed\n", count, failures);
+  return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,21 @@
+use std::{env, fs, path::Path};
+
+// The header generated by the build has to match the one committed in
+// `include/`. Run with `AOC_UPDATE_HEADER=1` to copy it over after changing the
+// interface.
+#[test]
+fn test_committed_header_is_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc.h"));
+    let committed = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/aoc.h");
+
+    if env::var_os("AOC_UPDATE_HEADER").is_some() {
+        fs::write(&committed, generated).unwrap();
+        return;
+    }
+
+    assert!(
+        fs::read_to_string(&committed).unwrap() == generated,
+        "{} is out of date, run the tests with AOC_UPDATE_HEADER=1 to regenerate it",
+        committed.display()
+    );
+}
@@ -0,0 +1,216 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+// Every `<year>/day_<n>_<name>` crate of the workspace that this crate depends
+// on becomes a registry entry. The list comes from `cargo metadata`, so the
+// dependency is the only place a day is named.
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let root = Path::new(&manifest_dir).parent().unwrap();
+    let common_dir = root.join("aoc_common");
+
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!(
+        "cargo:rerun-if-changed={}",
+        root.join("Cargo.toml").display()
+    );
+    println!(
+        "cargo:rerun-if-changed={}",
+        root.join("Cargo.lock").display()
+    );
+    println!("cargo:rerun-if-changed={}", common_dir.display());
+
+    let metadata = metadata(root);
+    let packages: HashMap<&str, &Value> = metadata["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|package| (package["id"].as_str().unwrap(), package))
+        .collect();
+    let nodes: HashMap<&str, &Value> = metadata["resolve"]["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| (node["id"].as_str().unwrap(), node))
+        .collect();
+
+    let registry = metadata["workspace_members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(Value::as_str)
+        .find(|id| packages[id]["name"] == "aoc_registry")
+        .unwrap();
+    let dependencies: HashMap<&str, &str> = nodes[registry]["deps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dep| (dep["pkg"].as_str().unwrap(), dep["name"].as_str().unwrap()))
+        .collect();
+
+    let mut entries = vec![];
+
+    for id in metadata["workspace_members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(Value::as_str)
+    {
+        let crate_dir = Path::new(packages[id]["manifest_path"].as_str().unwrap())
+            .parent()
+            .unwrap();
+        let Some((year, day)) = location(crate_dir) else {
+            continue;
+        };
+
+        println!("cargo:rerun-if-changed={}", crate_dir.display());
+
+        let Some(crate_name) = dependencies.get(id) else {
+            println!(
+                "cargo:warning={} is not registered, add it to the aoc_registry dependencies",
+                crate_dir.strip_prefix(root).unwrap_or(crate_dir).display()
+            );
+            continue;
+        };
+
+        let mut hasher = Sha256::new();
+        hash_sources(&mut hasher, &[crate_dir, &common_dir]);
+        hash_dependencies(&mut hasher, id, &packages, &nodes);
+        let fingerprint = format!("{:x}", hasher.finalize());
+
+        entries.push((year, day, crate_name.to_string(), fingerprint));
+    }
+
+    entries.sort();
+
+    let puzzles: String = entries
+        .iter()
+        .map(|(_, _, crate_name, _)| format!("    &{crate_name}::PUZZLE,\n"))
+        .collect();
+    let locations: String = entries
+        .iter()
+        .map(|(year, day, _, _)| format!("    ({year}, {day}),\n"))
+        .collect();
+    let fingerprints: String = entries
+        .iter()
+        .map(|(_, _, _, fingerprint)| format!("    \"{fingerprint}\",\n"))
+        .collect();
+
+    let registry = format!(
+        "pub static PUZZLES: &[&Puzzle] = &[\n{puzzles}];\n\n\
+         static FINGERPRINTS: &[&str] = &[\n{fingerprints}];\n\n\
+         #[cfg(test)]\n\
+         static LOCATIONS: &[(u32, u32)] = &[\n{locations}];\n"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
+
+fn metadata(root: &Path) -> Value {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--offline",
+            "--manifest-path",
+        ])
+        .arg(root.join("Cargo.toml"))
+        .output()
+        .unwrap();
+    if !output.status.success() {
+        panic!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+// The year and day of a crate in `<year>/day_<n>_<name>`.
+fn location(crate_dir: &Path) -> Option<(u32, u32)> {
+    let year = crate_dir.parent()?.file_name()?.to_str()?.parse().ok()?;
+    let day = crate_dir
+        .file_name()?
+        .to_str()?
+        .strip_prefix("day_")?
+        .split('_')
+        .next()?
+        .parse()
+        .ok()?;
+
+    Some((year, day))
+}
+
+// A solution's answers can only change when its own crate, the shared
+// `aoc_common` code or one of the crates it is built with does, so those
+// sources and the versions locked for them identify the build of a solution.
+fn hash_sources(hasher: &mut Sha256, crate_dirs: &[&Path]) {
+    for crate_dir in crate_dirs {
+        let mut files = vec![crate_dir.join("Cargo.toml")];
+        source_files(&crate_dir.join("src"), &mut files);
+        files.sort();
+
+        for file in files {
+            let name = file.strip_prefix(crate_dir).unwrap();
+            hasher.update(name.to_string_lossy().as_bytes());
+            hasher.update(fs::read(&file).unwrap());
+        }
+    }
+}
+
+// Every crate the day is built with, however indirectly, by name, version and
+// source. Local crates are left at their name, as their sources move around.
+fn hash_dependencies(
+    hasher: &mut Sha256,
+    id: &str,
+    packages: &HashMap<&str, &Value>,
+    nodes: &HashMap<&str, &Value>,
+) {
+    let mut seen = BTreeSet::new();
+    let mut pending = vec![id];
+
+    while let Some(id) = pending.pop() {
+        for dep in nodes[id]["deps"].as_array().unwrap() {
+            let built_with = dep["dep_kinds"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|kind| kind["kind"] != "dev");
+            let pkg = dep["pkg"].as_str().unwrap();
+            if built_with && seen.insert(pkg) {
+                pending.push(pkg);
+            }
+        }
+    }
+
+    for pkg in seen {
+        let package = packages[pkg];
+        let source = package["source"].as_str().unwrap_or("local");
+        hasher.update(format!(
+            "{} {} {source}\n",
+            package["name"], package["version"]
+        ));
+    }
+}
+
+fn source_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            source_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    collections::BTreeMap,
+    sync::{PoisonError, RwLock},
+};
+
+use anyhow::{anyhow, Result};
+use aoc_common::puzzle::Puzzle;
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// The built-in puzzles and those registered at runtime.
+struct Registry {
+    // Puzzles loaded at runtime with their fingerprints. They take the place
+    // of a built-in puzzle for the same day.
+    plugins: RwLock<Vec<(&'static Puzzle, &'static str)>>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry {
+            plugins: RwLock::new(vec![]),
+        }
+    }
+
+    fn register(&self, puzzle: &'static Puzzle, fingerprint: &'static str) {
+        let mut plugins = self.plugins.write().unwrap_or_else(PoisonError::into_inner);
+
+        plugins.retain(|(registered, _)| {
+            (registered.year, registered.day) != (puzzle.year, puzzle.day)
+        });
+        plugins.push((puzzle, fingerprint));
+    }
+
+    /// Every puzzle with its fingerprint, built-in ones first.
+    fn registered(&self) -> Vec<(&'static Puzzle, &'static str)> {
+        let plugins = self.plugins.read().unwrap_or_else(PoisonError::into_inner);
+
+        PUZZLES
+            .iter()
+            .copied()
+            .zip(FINGERPRINTS.iter().copied())
+            .filter(|(puzzle, _)| {
+                !plugins
+                    .iter()
+                    .any(|(plugin, _)| (plugin.year, plugin.day) == (puzzle.year, puzzle.day))
+            })
+            .chain(plugins.iter().copied())
+            .collect()
+    }
+
+    fn years(&self) -> BTreeMap<u32, Vec<&'static Puzzle>> {
+        let mut years: BTreeMap<u32, Vec<&'static Puzzle>> = BTreeMap::new();
+
+        for (puzzle, _) in self.registered() {
+            years.entry(puzzle.year).or_default().push(puzzle);
+        }
+        for puzzles in years.values_mut() {
+            puzzles.sort_by_key(|puzzle| puzzle.day);
+        }
+
+        years
+    }
+
+    fn find(&self, year: u32, day: u32) -> Result<&'static Puzzle> {
+        self.registered()
+            .into_iter()
+            .map(|(puzzle, _)| puzzle)
+            .find(|puzzle| puzzle.year == year && puzzle.day == day)
+            .ok_or(anyhow!("Day {day} of {year} has not been solved yet"))
+    }
+
+    fn fingerprint(&self, puzzle: &Puzzle) -> &'static str {
+        self.registered()
+            .into_iter()
+            .find(|(registered, _)| registered.year == puzzle.year && registered.day == puzzle.day)
+            .map_or("", |(_, fingerprint)| fingerprint)
+    }
+}
+
+static REGISTRY: Registry = Registry::new();
+
+/// Adds a puzzle that was not built in, such as one loaded from a plugin.
+pub fn register(puzzle: &'static Puzzle, fingerprint: &'static str) {
+    REGISTRY.register(puzzle, fingerprint);
+}
+
+pub fn years() -> BTreeMap<u32, Vec<&'static Puzzle>> {
+    REGISTRY.years()
+}
+
+pub fn latest_year() -> Option<u32> {
+    REGISTRY
+        .registered()
+        .iter()
+        .map(|(puzzle, _)| puzzle.year)
+        .max()
+}
+
+pub fn find(year: u32, day: u32) -> Result<&'static Puzzle> {
+    REGISTRY.find(year, day)
+}
+
+/// The puzzle built in for the day, even when a plugin has taken its place.
+pub fn built_in(year: u32, day: u32) -> Option<&'static Puzzle> {
+    PUZZLES
+        .iter()
+        .copied()
+        .find(|puzzle| puzzle.year == year && puzzle.day == day)
+}
+
+/// Hash of the sources a puzzle's solution is built from, which changes
+/// whenever its answers could. A plugin's is the hash of its library.
+pub fn fingerprint(puzzle: &Puzzle) -> &'static str {
+    REGISTRY.fingerprint(puzzle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzles_match_their_directories() {
+        let registered: Vec<(u32, u32)> = PUZZLES
+            .iter()
+            .map(|puzzle| (puzzle.year, puzzle.day))
+            .collect();
+
+        assert_eq!(registered, LOCATIONS);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(2023, 7).unwrap().title, "Camel Cards");
+        assert!(find(2023, 26).is_err());
+        assert!(find(2015, 1).is_err());
+    }
+
+    #[test]
+    fn test_years() {
+        let years = years();
+
+        assert_eq!(years[&2023].len(), 9);
+        assert_eq!(latest_year(), Some(2023));
+    }
+
+    #[test]
+    fn test_registered_puzzle_replaces_built_in() {
+        static REPLACEMENT: Puzzle = Puzzle {
+            title: "Mirage Maintenance, again",
+            ..day_9_mirage_maintenance::PUZZLE
+        };
+        let registry = Registry::new();
+        registry.register(&REPLACEMENT, "plugin");
+
+        assert_eq!(registry.find(2023, 9).unwrap().title, REPLACEMENT.title);
+        assert_eq!(registry.fingerprint(&REPLACEMENT), "plugin");
+        assert_eq!(registry.years()[&2023].len(), 9);
+        assert_eq!(registry.years()[&2023][8].title, REPLACEMENT.title);
+        assert_eq!(find(2023, 9).unwrap().title, "Mirage Maintenance");
+        assert_eq!(built_in(2023, 9).unwrap().title, "Mirage Maintenance");
+        assert!(built_in(2023, 26).is_none());
+    }
+
+    #[test]
+    fn test_fingerprints_differ_between_days() {
+        let day_1 = fingerprint(find(2023, 1).unwrap());
+        let day_2 = fingerprint(find(2023, 2).unwrap());
+
+        assert_eq!(day_1.len(), 64);
+        assert_ne!(day_1, day_2);
+    }
+}